@@ -0,0 +1,103 @@
+//! Sums of terms and products modulo the modulus of a type implementing `ModularInteger`, with
+//! deferred reduction.
+
+use crate::traits::ModularInteger;
+use num::BigUint;
+use std::marker::PhantomData;
+
+/// Number of bits the unreduced sum may grow above the bits of a product of two elements before
+/// the accumulator reduces it.
+pub const DEFAULT_HEADROOM: usize = 64;
+
+/// Accumulates terms and products of elements without reducing them, and reduces only when the
+/// sum exceeds its headroom or on `finish`.
+///
+/// ```
+/// # use abstract_integers::*;
+/// # use num::{BigUint, CheckedSub, Zero};
+/// # use std::num::ParseIntError;
+/// # use std::ops::*;
+/// # define_abstract_integer_checked!(BigBounded, 256);
+/// # define_refined_modular_integer!(
+/// #     Felem,
+/// #     BigBounded,
+/// #     BigBounded::pow2(255) - BigBounded::from_literal(19)
+/// # );
+/// # let coeffs = [Felem::from_literal(1), Felem::from_literal(2)];
+/// # let powers = abstract_integers::powers(Felem::from_literal(3));
+/// let mut acc = Accumulator::<Felem>::new();
+/// for (a, b) in coeffs.iter().zip(powers) {
+///     acc.add_product(*a, b);
+/// }
+/// let r: Felem = acc.finish();
+/// # assert_eq!(r, Felem::from_literal(7));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Accumulator<T> {
+    sum: BigUint,
+    modulus: BigUint,
+    limit: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<T> Accumulator<T>
+where
+    T: ModularInteger + From<BigUint> + Into<BigUint>,
+{
+    /// Creates an empty accumulator with `DEFAULT_HEADROOM` bits of headroom.
+    pub fn new() -> Self {
+        Self::with_headroom(DEFAULT_HEADROOM)
+    }
+
+    /// Creates an empty accumulator reducing the sum when it exceeds the bits of a product of
+    /// two elements by more than `headroom` bits.
+    pub fn with_headroom(headroom: usize) -> Self {
+        let modulus = T::modulus();
+        let limit = 2 * modulus.bits() + headroom;
+        Accumulator {
+            sum: BigUint::from(0u32),
+            modulus,
+            limit,
+            phantom: PhantomData,
+        }
+    }
+
+    fn reduce_if_needed(&mut self) {
+        if self.sum.bits() > self.limit {
+            self.sum %= &self.modulus;
+        }
+    }
+
+    /// Adds `x` to the sum.
+    pub fn add(&mut self, x: T) {
+        self.sum += x.into();
+        self.reduce_if_needed();
+    }
+
+    /// Adds `a * b` to the sum, without reducing the product.
+    pub fn add_product(&mut self, a: T, b: T) {
+        let a: BigUint = a.into();
+        let b: BigUint = b.into();
+        self.sum += a * b;
+        self.reduce_if_needed();
+    }
+
+    /// Returns the sum reduced modulo the modulus, without consuming the accumulator.
+    pub fn value(&self) -> T {
+        (&self.sum % &self.modulus).into()
+    }
+
+    /// Returns the sum reduced modulo the modulus.
+    pub fn finish(self) -> T {
+        (self.sum % self.modulus).into()
+    }
+}
+
+impl<T> Default for Accumulator<T>
+where
+    T: ModularInteger + From<BigUint> + Into<BigUint>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
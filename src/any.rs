@@ -0,0 +1,101 @@
+//! Type-erased values of the types generated by the macros of this crate, to store values of
+//! different types in the same collection, for instance test vectors of mixed widths.
+//!
+//! ```
+//! # use abstract_integers::*;
+//! # use num::{BigUint, CheckedSub, Zero};
+//! # use std::num::ParseIntError;
+//! # use std::ops::*;
+//! # define_abstract_integer_checked!(BigBounded, 256);
+//! # define_refined_modular_integer!(
+//! #     Felem,
+//! #     BigBounded,
+//! #     BigBounded::pow2(255) - BigBounded::from_literal(19)
+//! # );
+//! # define_abstract_integer_checked!(Scalar, 512);
+//! let values = vec![AnyInt::new(Felem::from_literal(3)), AnyInt::new(Scalar::from_literal(4))];
+//! assert_eq!(values[0].type_name(), "Felem");
+//! let x: Option<Felem> = values[0].downcast();
+//! # assert_eq!(x, Some(Felem::from_literal(3)));
+//! ```
+
+use num::BigUint;
+use std::any::{Any, TypeId};
+use std::fmt;
+
+/// Dyn-compatible interface of the generated types, implemented by the macros for all the types
+/// but the secret ones, whose values must not be read or compared through it.
+pub trait AnyAbstractInt: Any + fmt::Debug + fmt::Display + Send + Sync {
+    /// Name of the type, as given to the macro.
+    fn type_name(&self) -> &'static str;
+    /// The value of the integer.
+    fn to_biguint(&self) -> BigUint;
+    fn as_any(&self) -> &dyn Any;
+    fn clone_box(&self) -> Box<dyn AnyAbstractInt>;
+}
+
+/// A value of any of the generated types, remembering its type.
+pub struct AnyInt(Box<dyn AnyAbstractInt>);
+
+impl AnyInt {
+    pub fn new<T: AnyAbstractInt>(x: T) -> Self {
+        AnyInt(Box::new(x))
+    }
+
+    /// Name of the type of the value.
+    pub fn type_name(&self) -> &'static str {
+        self.0.type_name()
+    }
+
+    /// The `TypeId` of the type of the value.
+    pub fn type_id(&self) -> TypeId {
+        self.0.as_any().type_id()
+    }
+
+    /// The value of the integer, whatever its type.
+    pub fn value(&self) -> BigUint {
+        self.0.to_biguint()
+    }
+
+    /// Returns `true` if the value has type `T`.
+    pub fn is<T: AnyAbstractInt>(&self) -> bool {
+        self.0.as_any().is::<T>()
+    }
+
+    /// Returns a reference to the value if it has type `T`.
+    pub fn downcast_ref<T: AnyAbstractInt>(&self) -> Option<&T> {
+        self.0.as_any().downcast_ref::<T>()
+    }
+
+    /// Returns the value if it has type `T`.
+    pub fn downcast<T: AnyAbstractInt + Copy>(&self) -> Option<T> {
+        self.downcast_ref::<T>().copied()
+    }
+}
+
+impl Clone for AnyInt {
+    fn clone(&self) -> Self {
+        AnyInt(self.0.clone_box())
+    }
+}
+
+/// Values are equal if they have the same type and the same value.
+impl PartialEq for AnyInt {
+    fn eq(&self, other: &AnyInt) -> bool {
+        self.type_id() == other.type_id() && self.value() == other.value()
+    }
+}
+
+impl Eq for AnyInt {}
+
+impl fmt::Display for AnyInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Debug for AnyInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}({:?})", self.type_name(), self.0)
+    }
+}
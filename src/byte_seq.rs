@@ -0,0 +1,162 @@
+//! Sequences of 8-bit machine words, for the specifications of block-based algorithms.
+//!
+//! `ByteSeq<T>` holds words of a type defined with `define_machine_integer` with 8 bits, so that
+//! sequences of secret bytes stay secret: the words are redacted when printed and cannot be
+//! compared, and it is a compile-time error to convert secret bytes into a public integer or a
+//! secret integer into public bytes.
+//!
+//! ```
+//! # use abstract_integers::*;
+//! # use num::{BigUint, CheckedSub, Zero};
+//! # use std::num::ParseIntError;
+//! # use std::ops::*;
+//! define_machine_integer!(SecretU8, 8, secret);
+//! define_abstract_integer_checked!(Block, 128, secret);
+//! let msg = ByteSeq::<SecretU8>::from_public_slice(b"a message of several blocks");
+//! for chunk in msg.chunks(16) {
+//!     let block: Block = chunk.to_integer_be();
+//! }
+//! ```
+
+use crate::traits::{CheckedInteger, MachineInteger};
+use num::{BigUint, ToPrimitive};
+use std::ops::{Index, IndexMut};
+
+/// A sequence of bytes of the 8-bit machine type `T`.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ByteSeq<T>(Vec<T>);
+
+impl<T: MachineInteger> ByteSeq<T> {
+    /// Returns a sequence of `len` zero bytes.
+    pub fn new(len: usize) -> Self {
+        Self::from_vec(vec![T::default(); len])
+    }
+
+    /// Returns the sequence of the given bytes.
+    pub fn from_vec(v: Vec<T>) -> Self {
+        const { assert!(T::BITS == 8, "ByteSeq needs an 8-bit machine type") };
+        ByteSeq(v)
+    }
+
+    /// Returns the sequence of the bytes of a public slice, classifying them if `T` is secret.
+    pub fn from_public_slice(v: &[u8]) -> Self {
+        Self::from_vec(v.iter().map(|b| T::from(BigUint::from(*b))).collect())
+    }
+
+    /// Returns the bytes, explicitly making them public.
+    pub fn declassify(&self) -> Vec<u8> {
+        self.0
+            .iter()
+            .map(|b| b.declassify().to_u8().expect("8-bit machine type"))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.0.iter()
+    }
+
+    /// Returns the `len` bytes starting at `start`.
+    ///
+    /// **Warning**: panics if the range is out of the sequence.
+    pub fn slice(&self, start: usize, len: usize) -> Self {
+        ByteSeq(self.0[start..start + len].to_vec())
+    }
+
+    /// Returns the sequence followed by `other`.
+    pub fn concat(&self, other: &Self) -> Self {
+        let mut v = self.0.clone();
+        v.extend_from_slice(&other.0);
+        ByteSeq(v)
+    }
+
+    /// Number of chunks of `chunk_len` bytes, the last one being possibly shorter.
+    ///
+    /// **Warning**: panics if `chunk_len` is 0.
+    pub fn num_chunks(&self, chunk_len: usize) -> usize {
+        self.0.len().div_ceil(chunk_len)
+    }
+
+    /// Iterates over the chunks of `chunk_len` bytes, the last one being possibly shorter.
+    ///
+    /// **Warning**: panics if `chunk_len` is 0.
+    pub fn chunks(&self, chunk_len: usize) -> impl Iterator<Item = ByteSeq<T>> + '_ {
+        self.0.chunks(chunk_len).map(|c| ByteSeq(c.to_vec()))
+    }
+
+    fn to_biguint_be(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.declassify())
+    }
+
+    /// Reads the sequence as a big-endian integer.
+    ///
+    /// It is a compile-time error to read secret bytes into a public type.
+    ///
+    /// **Warning**: panics if the value does not fit in the type.
+    pub fn to_integer_be<W>(&self) -> W
+    where
+        W: CheckedInteger + From<BigUint>,
+    {
+        const { assert!(!T::SECRET || W::SECRET, "secret bytes read into a public integer") };
+        self.to_biguint_be().into()
+    }
+
+    /// Reads the sequence as a little-endian integer, see `to_integer_be`.
+    pub fn to_integer_le<W>(&self) -> W
+    where
+        W: CheckedInteger + From<BigUint>,
+    {
+        const { assert!(!T::SECRET || W::SECRET, "secret bytes read into a public integer") };
+        let mut bytes = self.declassify();
+        bytes.reverse();
+        BigUint::from_bytes_be(&bytes).into()
+    }
+
+    /// Returns the big-endian encoding of `x` on `W::BYTES` bytes.
+    ///
+    /// It is a compile-time error to write a secret integer into public bytes.
+    pub fn from_integer_be<W>(x: W) -> Self
+    where
+        W: CheckedInteger + Into<BigUint>,
+    {
+        const { assert!(T::SECRET || !W::SECRET, "secret integer written into public bytes") };
+        let bytes = x.into().to_bytes_be();
+        let mut v = vec![0u8; W::BYTES - bytes.len()];
+        v.extend_from_slice(&bytes);
+        Self::from_public_slice(&v)
+    }
+
+    /// Returns the little-endian encoding of `x` on `W::BYTES` bytes, see `from_integer_be`.
+    pub fn from_integer_le<W>(x: W) -> Self
+    where
+        W: CheckedInteger + Into<BigUint>,
+    {
+        let mut s = Self::from_integer_be(x);
+        s.0.reverse();
+        s
+    }
+}
+
+impl<T> Index<usize> for ByteSeq<T> {
+    type Output = T;
+    fn index(&self, i: usize) -> &T {
+        &self.0[i]
+    }
+}
+
+impl<T> IndexMut<usize> for ByteSeq<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        &mut self.0[i]
+    }
+}
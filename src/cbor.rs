@@ -0,0 +1,136 @@
+//! Canonical CBOR encoding of natural integers (RFC 8949), used by the `to_cbor` and
+//! `from_cbor` methods of the generated types.
+//!
+//! Values below 2^64 are encoded as unsigned integers (major type 0), larger values as positive
+//! bignums (tag 2 followed by a byte string). The encoding follows the deterministic encoding
+//! rules, and decoding enforces them: heads must use the shortest form, bignums must not have
+//! leading zeros nor fit in 64 bits, and indefinite lengths are rejected.
+
+use crate::diagnostics::OutOfRangeError;
+use num::{BigUint, ToPrimitive};
+use std::fmt;
+
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TAG: u8 = 6;
+const TAG_POSITIVE_BIGNUM: u64 = 2;
+
+/// Error returned when decoding a CBOR integer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CborError {
+    /// The input ends before the end of the item.
+    Truncated,
+    /// The input continues after the end of the item.
+    TrailingBytes,
+    /// The item is not an unsigned integer nor a positive bignum.
+    UnexpectedType,
+    /// The item does not follow the deterministic encoding rules.
+    NonCanonical,
+    /// The integer is out of the range of the type.
+    OutOfRange(OutOfRangeError),
+}
+
+impl fmt::Display for CborError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CborError::Truncated => write!(f, "truncated CBOR item"),
+            CborError::TrailingBytes => write!(f, "trailing bytes after CBOR item"),
+            CborError::UnexpectedType => write!(f, "CBOR item is not a natural integer"),
+            CborError::NonCanonical => write!(f, "non-canonical CBOR encoding"),
+            CborError::OutOfRange(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for CborError {}
+
+fn write_head(out: &mut Vec<u8>, major: u8, n: u64) {
+    let major = major << 5;
+    if n < 24 {
+        out.push(major | n as u8);
+    } else if n <= 0xff {
+        out.extend_from_slice(&[major | 24, n as u8]);
+    } else if n <= 0xffff {
+        out.push(major | 25);
+        out.extend_from_slice(&(n as u16).to_be_bytes());
+    } else if n <= 0xffff_ffff {
+        out.push(major | 26);
+        out.extend_from_slice(&(n as u32).to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&n.to_be_bytes());
+    }
+}
+
+/// Reads a head, returning its major type and argument and advancing `input`.
+fn read_head(input: &mut &[u8]) -> Result<(u8, u64), CborError> {
+    let (&first, rest) = input.split_first().ok_or(CborError::Truncated)?;
+    let (major, info) = (first >> 5, first & 0x1f);
+    let len = match info {
+        0..=23 => 0,
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        _ => return Err(CborError::NonCanonical),
+    };
+    if rest.len() < len {
+        return Err(CborError::Truncated);
+    }
+    let n = if len == 0 {
+        info as u64
+    } else {
+        rest[..len].iter().fold(0u64, |acc, b| (acc << 8) | *b as u64)
+    };
+    let shortest = match len {
+        0 => true,
+        1 => n >= 24,
+        _ => n >> (4 * len) != 0,
+    };
+    if !shortest {
+        return Err(CborError::NonCanonical);
+    }
+    *input = &rest[len..];
+    Ok((major, n))
+}
+
+/// Returns the canonical CBOR encoding of `x`.
+pub fn encode(x: &BigUint) -> Vec<u8> {
+    let mut out = Vec::new();
+    if let Some(n) = x.to_u64() {
+        write_head(&mut out, MAJOR_UNSIGNED, n);
+    } else {
+        let bytes = x.to_bytes_be();
+        write_head(&mut out, MAJOR_TAG, TAG_POSITIVE_BIGNUM);
+        write_head(&mut out, MAJOR_BYTES, bytes.len() as u64);
+        out.extend_from_slice(&bytes);
+    }
+    out
+}
+
+/// Decodes a canonical CBOR unsigned integer or positive bignum, with nothing after it.
+pub fn decode(mut input: &[u8]) -> Result<BigUint, CborError> {
+    let value = match read_head(&mut input)? {
+        (MAJOR_UNSIGNED, n) => BigUint::from(n),
+        (MAJOR_TAG, TAG_POSITIVE_BIGNUM) => {
+            let len = match read_head(&mut input)? {
+                (MAJOR_BYTES, len) => len as usize,
+                _ => return Err(CborError::UnexpectedType),
+            };
+            if input.len() < len {
+                return Err(CborError::Truncated);
+            }
+            let (bytes, rest) = input.split_at(len);
+            if bytes.len() <= 8 || bytes[0] == 0 {
+                return Err(CborError::NonCanonical);
+            }
+            input = rest;
+            BigUint::from_bytes_be(bytes)
+        }
+        _ => return Err(CborError::UnexpectedType),
+    };
+    if !input.is_empty() {
+        return Err(CborError::TrailingBytes);
+    }
+    Ok(value)
+}
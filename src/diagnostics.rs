@@ -0,0 +1,288 @@
+//! Structured information about the arithmetic errors detected by the generated types.
+//!
+//! When an operation overflows, underflows or divides by zero, the generated types panic with an
+//! `ArithmeticPanic` payload describing the type, the operation and its operands. Harnesses can
+//! recover it with `std::panic::catch_unwind` and `downcast_ref::<ArithmeticPanic>()`. Since
+//! the default panic hook only prints string payloads, applications call `install_panic_hook`
+//! to get the description printed; the library never changes the panic hook by itself.
+//!
+//! Before panicking, the error is handed to the overflow hook registered for the type with
+//! `set_type_overflow_hook`, or else to the global one registered with `set_overflow_hook`. A
+//! hook can log the error and return a substitute value for the result of the operation instead
+//! of letting it panic.
+
+use num::BigUint;
+use std::any::TypeId;
+use std::fmt;
+use std::sync::{Arc, Once, RwLock};
+
+/// Handler for arithmetic errors, see the module documentation. Returning `None` lets the
+/// operation panic, returning `Some(x)` makes it evaluate to `x`.
+pub type OverflowHook = dyn Fn(&ArithmeticPanic) -> Option<BigUint> + Send + Sync;
+
+static GLOBAL_HOOK: RwLock<Option<Arc<OverflowHook>>> = RwLock::new(None);
+static TYPE_HOOKS: RwLock<Vec<(TypeId, Arc<OverflowHook>)>> = RwLock::new(Vec::new());
+
+/// Registers the hook invoked on arithmetic errors of types without a specific hook.
+pub fn set_overflow_hook<F>(hook: F)
+where
+    F: Fn(&ArithmeticPanic) -> Option<BigUint> + Send + Sync + 'static,
+{
+    *GLOBAL_HOOK.write().unwrap() = Some(Arc::new(hook));
+}
+
+/// Registers the hook invoked on arithmetic errors of type `T`, replacing any previous one.
+pub fn set_type_overflow_hook<T: 'static, F>(hook: F)
+where
+    F: Fn(&ArithmeticPanic) -> Option<BigUint> + Send + Sync + 'static,
+{
+    let mut hooks = TYPE_HOOKS.write().unwrap();
+    hooks.retain(|(id, _)| *id != TypeId::of::<T>());
+    hooks.push((TypeId::of::<T>(), Arc::new(hook)));
+}
+
+/// Removes the global hook and all the per-type hooks.
+pub fn clear_overflow_hooks() {
+    *GLOBAL_HOOK.write().unwrap() = None;
+    TYPE_HOOKS.write().unwrap().clear();
+}
+
+/// The kind of arithmetic error detected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArithmeticErrorKind {
+    /// The result is too big for the type.
+    Overflow,
+    /// The result is negative.
+    Underflow,
+    /// The right-hand side of a division or remainder is zero.
+    DivisionByZero,
+    /// Non-zero bits were shifted out of the type.
+    LostBits,
+}
+
+/// Panic payload describing an arithmetic error, see the module documentation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArithmeticPanic {
+    /// Name of the type in which the operation was performed.
+    pub type_name: &'static str,
+    /// The operator, e.g. `"+"` or `"<<"`.
+    pub operation: &'static str,
+    pub kind: ArithmeticErrorKind,
+    /// Values of the operands, in order, empty for secret types.
+    pub operands: Vec<BigUint>,
+    /// Whether the operation was performed in a secret type, whose operands are redacted.
+    pub secret: bool,
+}
+
+impl ArithmeticPanic {
+    pub fn new(
+        type_name: &'static str,
+        operation: &'static str,
+        kind: ArithmeticErrorKind,
+        operands: Vec<BigUint>,
+    ) -> Self {
+        ArithmeticPanic {
+            type_name,
+            operation,
+            kind,
+            operands,
+            secret: false,
+        }
+    }
+
+    /// Marks the operation as performed in a secret type if `secret` is true, dropping the
+    /// operands so that they are neither printed nor handed to the hooks.
+    pub fn secret(mut self, secret: bool) -> Self {
+        if secret {
+            self.secret = true;
+            self.operands.clear();
+        }
+        self
+    }
+
+    /// Panics with `self` as payload.
+    #[track_caller]
+    pub fn raise(self) -> ! {
+        std::panic::panic_any(self)
+    }
+
+    /// Hands `self` to the overflow hook of type `T` or to the global one, returning the
+    /// substitute value they provide or panicking with `self` as payload.
+    #[track_caller]
+    pub fn recover<T: 'static>(self) -> BigUint {
+        let type_hook = TYPE_HOOKS
+            .read()
+            .unwrap()
+            .iter()
+            .find(|(id, _)| *id == TypeId::of::<T>())
+            .map(|(_, hook)| hook.clone());
+        let hook = type_hook.or_else(|| GLOBAL_HOOK.read().unwrap().clone());
+        match hook.and_then(|hook| hook(&self)) {
+            Some(x) => x,
+            None => self.raise(),
+        }
+    }
+}
+
+impl fmt::Display for ArithmeticPanic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self.kind {
+            ArithmeticErrorKind::Overflow => "overflow",
+            ArithmeticErrorKind::Underflow => "underflow",
+            ArithmeticErrorKind::DivisionByZero => "division by zero",
+            ArithmeticErrorKind::LostBits => "bits shifted out",
+        };
+        write!(f, "bounded {} in type {}: ", kind, self.type_name)?;
+        if self.secret {
+            return write!(f, "{} on redacted secret operands", self.operation);
+        }
+        match self.operands.as_slice() {
+            [a, b] => write!(f, "{:#x} {} {:#x}", a, self.operation, b),
+            operands => {
+                write!(f, "{}(", self.operation)?;
+                for (i, x) in operands.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{:#x}", x)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Error returned by fallible conversions when the value does not fit in the target type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutOfRangeError {
+    /// Name of the target type.
+    pub type_name: &'static str,
+    /// The rejected value, zero for secret types.
+    pub value: BigUint,
+    /// Whether the target type is secret, in which case the value is redacted.
+    pub secret: bool,
+}
+
+impl OutOfRangeError {
+    pub fn new(type_name: &'static str, value: BigUint) -> Self {
+        OutOfRangeError {
+            type_name,
+            value,
+            secret: false,
+        }
+    }
+
+    /// Marks the target type as secret if `secret` is true, dropping the value, see
+    /// `ArithmeticPanic::secret`.
+    pub fn secret(mut self, secret: bool) -> Self {
+        if secret {
+            self.secret = true;
+            self.value = BigUint::from(0u32);
+        }
+        self
+    }
+}
+
+impl fmt::Display for OutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.secret {
+            return write!(f, "secret value out of the range of type {}", self.type_name);
+        }
+        write!(
+            f,
+            "value {:#x} out of the range of type {}",
+            self.value, self.type_name
+        )
+    }
+}
+
+impl std::error::Error for OutOfRangeError {}
+
+/// Error returned when building an element of a unit group from a value that is not coprime to
+/// the modulus.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NotAUnitError {
+    /// Name of the unit group.
+    pub type_name: &'static str,
+    /// The rejected value, zero for secret types.
+    pub value: BigUint,
+    /// Whether the ring is secret, in which case the value is redacted.
+    pub secret: bool,
+}
+
+impl NotAUnitError {
+    pub fn new(type_name: &'static str, value: BigUint) -> Self {
+        NotAUnitError {
+            type_name,
+            value,
+            secret: false,
+        }
+    }
+
+    /// Marks the ring as secret if `secret` is true, dropping the value, see
+    /// `ArithmeticPanic::secret`.
+    pub fn secret(mut self, secret: bool) -> Self {
+        if secret {
+            self.secret = true;
+            self.value = BigUint::from(0u32);
+        }
+        self
+    }
+}
+
+impl fmt::Display for NotAUnitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.secret {
+            return write!(f, "secret value is not a unit of {}", self.type_name);
+        }
+        write!(f, "value {:#x} is not a unit of {}", self.value, self.type_name)
+    }
+}
+
+impl std::error::Error for NotAUnitError {}
+
+/// Error returned by the conversions from `f64`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FromFloatError {
+    /// The float is NaN or infinite.
+    NotFinite,
+    /// The float is negative.
+    Negative,
+    /// The float has a fractional part.
+    NotInteger,
+    /// The float is an integer too big for the type.
+    OutOfRange(OutOfRangeError),
+}
+
+impl fmt::Display for FromFloatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromFloatError::NotFinite => write!(f, "float is not finite"),
+            FromFloatError::Negative => write!(f, "float is negative"),
+            FromFloatError::NotInteger => write!(f, "float is not an integer"),
+            FromFloatError::OutOfRange(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for FromFloatError {}
+
+static PANIC_HOOK: Once = Once::new();
+
+/// Wraps the current panic hook so that `ArithmeticPanic` payloads are printed with their
+/// description, other panics are handled by the previous hook. Only the first call has an
+/// effect.
+pub fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            match info.payload().downcast_ref::<ArithmeticPanic>() {
+                Some(payload) => match info.location() {
+                    Some(location) => eprintln!("panicked at {}:\n{}", location, payload),
+                    None => eprintln!("panicked:\n{}", payload),
+                },
+                None => previous(info),
+            }
+        }));
+    });
+}
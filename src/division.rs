@@ -0,0 +1,69 @@
+//! Division by a divisor fixed in advance, for the types implementing `CheckedInteger`.
+//!
+//! `DivisorContext` precomputes the Barrett reciprocal `floor(4^k / d)` of the divisor once,
+//! with `k` the bit width of the type. Each division then costs two multiplications and at most
+//! two corrective subtractions instead of a long division.
+
+use crate::traits::CheckedInteger;
+use num::BigUint;
+use std::marker::PhantomData;
+
+/// A divisor with its precomputed reciprocal, see the module documentation.
+///
+/// The number of corrective subtractions depends on the dividend, so the divisions are not
+/// constant-time.
+#[derive(Clone, Debug)]
+pub struct DivisorContext<T> {
+    divisor: BigUint,
+    reciprocal: BigUint,
+    shift: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<T> DivisorContext<T>
+where
+    T: CheckedInteger + From<BigUint> + Into<BigUint>,
+{
+    /// Precomputes the reciprocal of `divisor`.
+    ///
+    /// **Warning**: panics if `divisor` is 0.
+    pub fn new(divisor: T) -> Self {
+        let divisor: BigUint = divisor.into();
+        assert!(divisor != BigUint::from(0u32), "division by zero");
+        let shift = 2 * T::BITS;
+        let reciprocal = (BigUint::from(1u32) << shift) / &divisor;
+        DivisorContext {
+            divisor,
+            reciprocal,
+            shift,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the divisor.
+    pub fn divisor(&self) -> T {
+        self.divisor.clone().into()
+    }
+
+    /// Returns the quotient and the remainder of the division of `x` by the divisor.
+    pub fn div_rem(&self, x: T) -> (T, T) {
+        let x: BigUint = x.into();
+        let mut q = (&x * &self.reciprocal) >> self.shift;
+        let mut r = x - &q * &self.divisor;
+        while r >= self.divisor {
+            q += 1u32;
+            r -= &self.divisor;
+        }
+        (q.into(), r.into())
+    }
+
+    /// Returns the quotient of `x` by the divisor.
+    pub fn div(&self, x: T) -> T {
+        self.div_rem(x).0
+    }
+
+    /// Returns the remainder of `x` by the divisor.
+    pub fn rem(&self, x: T) -> T {
+        self.div_rem(x).1
+    }
+}
@@ -0,0 +1,89 @@
+//! Byte encodings of the field elements and scalars of RFC 7748 (X25519, X448) and RFC 8032
+//! (Ed25519), generic over the refined types of the fields. All the encodings are
+//! little-endian and of fixed length.
+
+use crate::traits::ModularInteger;
+use num::BigUint;
+
+fn encode_le<F: Into<BigUint>, const N: usize>(x: F) -> [u8; N] {
+    let mut out = [0u8; N];
+    let bytes = x.into().to_bytes_le();
+    out[..bytes.len()].copy_from_slice(&bytes);
+    out
+}
+
+/// `decodeScalar25519` of RFC 7748: clears the 3 low bits and the top bit, and sets bit 254.
+/// Also the pruning of the secret scalar of Ed25519 in RFC 8032.
+pub fn decode_scalar_25519(k: &[u8; 32]) -> BigUint {
+    let mut k = *k;
+    k[0] &= 248;
+    k[31] &= 127;
+    k[31] |= 64;
+    BigUint::from_bytes_le(&k)
+}
+
+/// `decodeScalar448` of RFC 7748: clears the 2 low bits and sets bit 447.
+pub fn decode_scalar_448(k: &[u8; 56]) -> BigUint {
+    let mut k = *k;
+    k[0] &= 252;
+    k[55] |= 128;
+    BigUint::from_bytes_le(&k)
+}
+
+/// `decodeUCoordinate` of RFC 7748 for X25519: the top bit is masked, and non-canonical values
+/// are accepted and reduced modulo the field modulus, as required by the RFC.
+pub fn decode_u_coordinate_25519<F: ModularInteger + From<BigUint>>(u: &[u8; 32]) -> F {
+    let mut u = *u;
+    u[31] &= 127;
+    F::from(BigUint::from_bytes_le(&u))
+}
+
+/// `decodeUCoordinate` of RFC 7748 for X448, accepting and reducing non-canonical values.
+pub fn decode_u_coordinate_448<F: ModularInteger + From<BigUint>>(u: &[u8; 56]) -> F {
+    F::from(BigUint::from_bytes_le(u))
+}
+
+/// `encodeUCoordinate` of RFC 7748: the canonical little-endian encoding of the field element,
+/// `N` being 32 for X25519 and 56 for X448.
+///
+/// **Warning**: panics if the value does not fit in `N` bytes.
+pub fn encode_u_coordinate<F: ModularInteger + Into<BigUint>, const N: usize>(u: F) -> [u8; N] {
+    encode_le(u)
+}
+
+/// Encodes an Ed25519 point from its y-coordinate and the sign (least significant bit) of its
+/// x-coordinate, as in section 5.1.2 of RFC 8032.
+pub fn encode_point_25519<F: ModularInteger + Into<BigUint>>(y: F, x_is_odd: bool) -> [u8; 32] {
+    let mut out: [u8; 32] = encode_le(y);
+    out[31] |= (x_is_odd as u8) << 7;
+    out
+}
+
+/// Decodes an Ed25519 point encoding into its y-coordinate and the sign of its x-coordinate, as
+/// in section 5.1.3 of RFC 8032. Returns `None` if the y-coordinate is not canonical, i.e. not
+/// below the field modulus. Recovering x, which may also fail, is left to the caller.
+pub fn decode_point_25519<F: ModularInteger + From<BigUint>>(bytes: &[u8; 32]) -> Option<(F, bool)> {
+    let mut y = *bytes;
+    let x_is_odd = y[31] >> 7 == 1;
+    y[31] &= 127;
+    let y = BigUint::from_bytes_le(&y);
+    if y >= F::modulus() {
+        return None;
+    }
+    Some((F::from(y), x_is_odd))
+}
+
+/// Encodes an Ed25519 scalar on 32 little-endian bytes.
+pub fn encode_scalar_25519<S: ModularInteger + Into<BigUint>>(s: S) -> [u8; 32] {
+    encode_le(s)
+}
+
+/// Decodes the scalar `S` of an Ed25519 signature, rejecting values that are not below the
+/// group order as required by section 5.1.7 of RFC 8032.
+pub fn decode_scalar_8032<S: ModularInteger + From<BigUint>>(bytes: &[u8; 32]) -> Option<S> {
+    let s = BigUint::from_bytes_le(bytes);
+    if s >= S::modulus() {
+        return None;
+    }
+    Some(S::from(s))
+}
@@ -0,0 +1,136 @@
+//! Exponentiation algorithms generic over the types implementing `ModularInteger`.
+
+use crate::traits::ModularInteger;
+use num::BigUint;
+
+/// Precomputed powers of a fixed base, accelerating repeated exponentiations of that base.
+///
+/// The exponent is split into windows of `window` bits, and the table stores
+/// `base^(d * 2^(window * i))` for every window position `i` and digit `d`. An exponentiation
+/// then costs one multiplication per window and no squaring.
+pub struct PowTable<T> {
+    window: usize,
+    table: Vec<Vec<T>>,
+}
+
+impl<T: ModularInteger> PowTable<T> {
+    /// Precomputes the powers of `base` for exponents of up to `exp_bits` bits, with 4-bit
+    /// windows.
+    pub fn new(base: T, exp_bits: usize) -> Self {
+        Self::with_window(base, exp_bits, 4)
+    }
+
+    /// Precomputes the powers of `base` for exponents of up to `exp_bits` bits, with windows of
+    /// `window` bits. The table holds `2^window * exp_bits / window` elements.
+    ///
+    /// **Warning**: panics if `window` is not between 1 and 8.
+    pub fn with_window(base: T, exp_bits: usize, window: usize) -> Self {
+        assert!(
+            (1..=8).contains(&window),
+            "window size {} is not between 1 and 8",
+            window
+        );
+        let mut table = Vec::new();
+        let mut window_base = base;
+        for _ in 0..exp_bits.div_ceil(window) {
+            let mut row = Vec::with_capacity(1 << window);
+            let mut power = T::one();
+            for _ in 0..(1 << window) {
+                row.push(power);
+                power = power * window_base;
+            }
+            window_base = power;
+            table.push(row);
+        }
+        PowTable { window, table }
+    }
+
+    /// Returns the base to the power of `exp`.
+    ///
+    /// **Warning**: panics if `exp` has more bits than the table was built for.
+    pub fn pow(&self, exp: &BigUint) -> T {
+        let digits = exp.to_radix_le(1 << self.window);
+        assert!(
+            digits.len() <= self.table.len(),
+            "exponent {} is too big for the precomputed table",
+            exp
+        );
+        digits
+            .iter()
+            .zip(self.table.iter())
+            .fold(T::one(), |acc, (d, row)| acc * row[*d as usize])
+    }
+}
+
+/// Computes `bases[0]^exps[0] * bases[1]^exps[1] * ...` with Straus' algorithm: the squarings
+/// are shared between all the exponentiations, which are processed in 4-bit windows.
+///
+/// **Warning**: panics if `bases` and `exps` have different lengths.
+pub fn multi_pow<T, E>(bases: &[T], exps: &[E]) -> T
+where
+    T: ModularInteger,
+    E: Copy + Into<BigUint>,
+{
+    const WINDOW: usize = 4;
+    assert_eq!(
+        bases.len(),
+        exps.len(),
+        "multi-exponentiation needs as many bases as exponents"
+    );
+    let tables: Vec<Vec<T>> = bases
+        .iter()
+        .map(|base| {
+            let mut row = Vec::with_capacity(1 << WINDOW);
+            let mut power = T::one();
+            for _ in 0..(1 << WINDOW) {
+                row.push(power);
+                power = power * *base;
+            }
+            row
+        })
+        .collect();
+    let digits: Vec<Vec<u8>> = exps
+        .iter()
+        .map(|exp| (*exp).into().to_radix_le(1 << WINDOW))
+        .collect();
+    let windows = digits.iter().map(|d| d.len()).max().unwrap_or(0);
+    let mut acc = T::one();
+    for i in (0..windows).rev() {
+        for _ in 0..WINDOW {
+            acc = acc.square();
+        }
+        for (row, d) in tables.iter().zip(digits.iter()) {
+            if let Some(d) = d.get(i) {
+                acc = acc * row[*d as usize];
+            }
+        }
+    }
+    acc
+}
+
+/// Infinite iterator over the successive powers `1, x, x^2, x^3, ...` of an element, returned by
+/// `powers`.
+#[derive(Clone, Debug)]
+pub struct Powers<T> {
+    base: T,
+    next: T,
+}
+
+impl<T: ModularInteger> Iterator for Powers<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let current = self.next;
+        self.next = current * self.base;
+        Some(current)
+    }
+}
+
+/// Returns the successive powers of `x`, starting with `x^0 = 1`, with one multiplication per
+/// power.
+pub fn powers<T: ModularInteger>(x: T) -> Powers<T> {
+    Powers {
+        base: x,
+        next: T::one(),
+    }
+}
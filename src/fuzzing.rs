@@ -0,0 +1,153 @@
+//! Fuzzing entry points comparing the operators of a generated type with plain `BigUint`
+//! arithmetic. The functions take the raw input of the fuzzer, so they can be used directly as
+//! the body of a `fuzz_target!`:
+//!
+//! ```
+//! # use abstract_integers::*;
+//! # use num::{BigUint, CheckedSub, Zero};
+//! # use std::num::ParseIntError;
+//! # use std::ops::*;
+//! # define_abstract_integer_checked!(BigBounded, 256);
+//! # define_refined_modular_integer!(
+//! #     Felem,
+//! #     BigBounded,
+//! #     BigBounded::pow2(255) - BigBounded::from_literal(19)
+//! # );
+//! # macro_rules! fuzz_target {
+//! #     (|$data:ident: &[u8]| $body:expr) => {
+//! #         let target = |$data: &[u8]| $body;
+//! #         target(&[0xff; 64]);
+//! #     };
+//! # }
+//! fuzz_target!(|data: &[u8]| abstract_integers::fuzzing::fuzz_modular::<Felem>(data));
+//! ```
+//!
+//! Fuzzers abort on the first panic, so the operations that are expected to panic (overflow,
+//! division by zero) are not executed; only the results of the valid operations are compared.
+
+use crate::shadow::reference;
+use crate::traits::{CheckedInteger, ModularInteger};
+use num::{BigUint, Zero};
+
+type Op<T> = (&'static str, fn(T, T) -> T);
+
+/// Decodes two big-endian operands from the two halves of the input.
+pub fn split_operands(data: &[u8]) -> (BigUint, BigUint) {
+    let (a, b) = data.split_at(data.len() / 2);
+    (BigUint::from_bytes_be(a), BigUint::from_bytes_be(b))
+}
+
+fn compare<T: Copy + Into<BigUint>>(
+    type_name: &str,
+    op: &str,
+    a: &BigUint,
+    b: &BigUint,
+    got: T,
+    expected: BigUint,
+) {
+    let got: BigUint = got.into();
+    assert!(
+        got == expected,
+        "{}: {:#x} {} {:#x} computed {:#x}, expected {:#x}",
+        type_name,
+        a,
+        op,
+        b,
+        got,
+        expected
+    );
+}
+
+/// Compares `+`, `-`, `*`, `/` and `%` of a checked type with `BigUint` on the operands
+/// decoded from `data`, reduced to the range of the type.
+///
+/// **Warning**: panics if an operation disagrees with `BigUint`.
+pub fn fuzz_checked<T>(data: &[u8])
+where
+    T: CheckedInteger + From<BigUint> + Into<BigUint>,
+    T: std::ops::Div<Output = T> + std::ops::Rem<Output = T>,
+{
+    let bound = T::max_value() + 1u32;
+    let (a, b) = split_operands(data);
+    let (a, b) = (a % &bound, b % &bound);
+    let (x, y) = (T::from(a.clone()), T::from(b.clone()));
+    let ops: [Op<T>; 5] = [
+        ("+", |x, y| x + y),
+        ("-", |x, y| x - y),
+        ("*", |x, y| x * y),
+        ("/", |x, y| x / y),
+        ("%", |x, y| x % y),
+    ];
+    for (op, f) in ops.iter() {
+        let valid = match *op {
+            "-" => a >= b,
+            "/" | "%" => !b.is_zero(),
+            _ => true,
+        };
+        if !valid {
+            continue;
+        }
+        let expected = reference(op, &a, &b);
+        if expected < bound {
+            compare(std::any::type_name::<T>(), op, &a, &b, f(x, y), expected);
+        }
+    }
+}
+
+/// Compares `+`, `-`, `*`, `/` and `%` of a refined type with `BigUint` arithmetic modulo the
+/// modulus, on the operands decoded from `data`. For types defined with `field_div`, `/` is
+/// compared with the multiplication by the inverse, which requires a prime modulus.
+///
+/// **Warning**: panics if an operation disagrees with `BigUint`.
+pub fn fuzz_modular<T>(data: &[u8])
+where
+    T: ModularInteger + From<BigUint> + Into<BigUint>,
+    T: std::ops::Div<Output = T> + std::ops::Rem<Output = T>,
+{
+    let m = T::modulus();
+    let (a, b) = split_operands(data);
+    let (a, b) = (a % &m, b % &m);
+    let (x, y) = (T::from(a.clone()), T::from(b.clone()));
+    let ops: [Op<T>; 5] = [
+        ("+", |x, y| x + y),
+        ("-", |x, y| x - y),
+        ("*", |x, y| x * y),
+        ("/", |x, y| x / y),
+        ("%", |x, y| x % y),
+    ];
+    for (op, f) in ops.iter() {
+        let expected = match *op {
+            "-" => (&a + &m - &b) % &m,
+            "/" | "%" if b.is_zero() => continue,
+            "/" if T::FIELD_DIVISION => &a * b.modpow(&(&m - 2u32), &m) % &m,
+            _ => reference(op, &a, &b) % &m,
+        };
+        compare(std::any::type_name::<T>(), op, &a, &b, f(x, y), expected);
+    }
+}
+
+/// Same as `fuzz_modular`, also checking that `inv` returns the inverse of the first operand
+/// when it is not zero. Only meaningful for prime moduli.
+///
+/// **Warning**: panics if an operation disagrees with `BigUint`.
+pub fn fuzz_field<T>(data: &[u8])
+where
+    T: ModularInteger + From<BigUint> + Into<BigUint>,
+    T: std::ops::Div<Output = T> + std::ops::Rem<Output = T>,
+{
+    fuzz_modular::<T>(data);
+    let (a, _) = split_operands(data);
+    let a = a % T::modulus();
+    if !a.is_zero() {
+        let x = T::from(a.clone());
+        let inv: BigUint = x.inv().into();
+        compare(
+            std::any::type_name::<T>(),
+            "*",
+            &a,
+            &inv,
+            x * x.inv(),
+            BigUint::from(1u32),
+        );
+    }
+}
@@ -0,0 +1,43 @@
+//! Support for the index types defined with `define_bounded_index`.
+
+use std::marker::PhantomData;
+
+/// An integer type whose values are all strictly below `BOUND`.
+pub trait BoundedIndex: Copy {
+    const BOUND: usize;
+
+    fn as_usize(self) -> usize;
+}
+
+/// Compile-time check that an array of length `LEN` can be indexed by all the values of `I`.
+/// Referencing `OK` fails to compile otherwise.
+struct ArrayFits<I, const LEN: usize>(PhantomData<I>);
+
+impl<I: BoundedIndex, const LEN: usize> ArrayFits<I, LEN> {
+    const OK: () = assert!(
+        LEN >= I::BOUND,
+        "array too short to be indexed by the bounded index type"
+    );
+}
+
+/// Array accesses that are proved in range by the type of the index: `at` does not compile if
+/// the array is shorter than the bound of the index type.
+pub trait ArrayIndexExt<T> {
+    fn at<I: BoundedIndex>(&self, i: I) -> &T;
+
+    fn at_mut<I: BoundedIndex>(&mut self, i: I) -> &mut T;
+}
+
+impl<T, const N: usize> ArrayIndexExt<T> for [T; N] {
+    #[allow(clippy::let_unit_value)]
+    fn at<I: BoundedIndex>(&self, i: I) -> &T {
+        let () = ArrayFits::<I, N>::OK;
+        &self[i.as_usize()]
+    }
+
+    #[allow(clippy::let_unit_value)]
+    fn at_mut<I: BoundedIndex>(&mut self, i: I) -> &mut T {
+        let () = ArrayFits::<I, N>::OK;
+        &mut self[i.as_usize()]
+    }
+}
@@ -0,0 +1,72 @@
+//! Montgomery ladder computing scalar multiplications on the x-coordinate of Montgomery curves
+//! `y^2 = x^3 + A x^2 + x`, as specified by RFC 7748 for X25519 and X448, generic over the field
+//! type:
+//!
+//! ```
+//! # use abstract_integers::*;
+//! # use num::{BigUint, CheckedSub, Zero};
+//! # use std::num::ParseIntError;
+//! # use std::ops::*;
+//! # define_abstract_integer_checked!(BigBounded, 256);
+//! # define_refined_modular_integer!(
+//! #     Felem,
+//! #     BigBounded,
+//! #     BigBounded::pow2(255) - BigBounded::from_literal(19)
+//! # );
+//! # let (k, u) = (BigUint::from(1u32), Felem::from_literal(9));
+//! let a24 = Felem::from_literal(121665);
+//! let shared = ladder::ladder(&k, 255, u, a24);
+//! # assert_eq!(shared, u);
+//! ```
+//!
+//! The ladder has a constant structure: it performs the same sequence of field operations for
+//! all the scalars of `k_bits` bits, the conditional swaps being computed arithmetically.
+
+use crate::traits::ModularInteger;
+use num::BigUint;
+
+/// Swaps `a` and `b` if `swap` is 1, leaves them unchanged if it is 0, with the same field
+/// operations in both cases.
+pub fn cswap<F: ModularInteger>(swap: F, a: &mut F, b: &mut F) {
+    let dummy = swap * (*a - *b);
+    *a = *a - dummy;
+    *b = *b + dummy;
+}
+
+/// Returns the x-coordinate of `[k] P`, where `u` is the x-coordinate of `P` and
+/// `a24 = (A - 2) / 4` is the curve constant (121665 for Curve25519, 39081 for Curve448). The
+/// scalar is processed as a `k_bits`-bit value, most significant bit first; decoding and
+/// clamping it is left to the caller.
+pub fn ladder<F: ModularInteger>(k: &BigUint, k_bits: usize, u: F, a24: F) -> F {
+    let x_1 = u;
+    let (mut x_2, mut z_2) = (F::one(), F::zero());
+    let (mut x_3, mut z_3) = (u, F::one());
+    let mut swap = F::zero();
+    let k = k.to_bytes_le();
+    for t in (0..k_bits).rev() {
+        let bit = k.get(t / 8).map_or(0, |byte| (byte >> (t % 8)) & 1);
+        let k_t = if bit == 1 { F::one() } else { F::zero() };
+        // swap ^= k_t, on field elements equal to 0 or 1
+        swap = swap + k_t - (swap + swap) * k_t;
+        cswap(swap, &mut x_2, &mut x_3);
+        cswap(swap, &mut z_2, &mut z_3);
+        swap = k_t;
+
+        let a = x_2 + z_2;
+        let aa = a.square();
+        let b = x_2 - z_2;
+        let bb = b.square();
+        let e = aa - bb;
+        let c = x_3 + z_3;
+        let d = x_3 - z_3;
+        let da = d * a;
+        let cb = c * b;
+        x_3 = (da + cb).square();
+        z_3 = x_1 * (da - cb).square();
+        x_2 = aa * bb;
+        z_2 = e * (aa + a24 * e);
+    }
+    cswap(swap, &mut x_2, &mut x_3);
+    cswap(swap, &mut z_2, &mut z_3);
+    x_2 * z_2.inv()
+}
@@ -0,0 +1,96 @@
+//! Test harness checking the algebraic laws of the types implementing `ModularInteger`, to be
+//! called from the tests of the crates defining them:
+//!
+//! ```
+//! # use abstract_integers::*;
+//! # use num::{BigUint, CheckedSub, Zero};
+//! # use std::num::ParseIntError;
+//! # use std::ops::*;
+//! # define_abstract_integer_checked!(BigBounded, 256);
+//! # define_refined_modular_integer!(
+//! #     Felem,
+//! #     BigBounded,
+//! #     BigBounded::pow2(255) - BigBounded::from_literal(19)
+//! # );
+//! abstract_integers::laws::assert_ring_axioms::<Felem>();
+//! let mut state = 0x1234_5678_9abc_def0u64;
+//! abstract_integers::laws::assert_field_axioms::<Felem, _>(|| {
+//!     state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+//!     Felem::from(state)
+//! });
+//! ```
+
+use crate::traits::ModularInteger;
+use num::BigUint;
+use std::fmt::Debug;
+
+/// Number of random triples checked by `assert_field_axioms`.
+pub const ROUNDS: usize = 64;
+
+/// Values around the edges of the type: small values, values just below the modulus, half the
+/// modulus and a large power.
+fn edge_samples<T: ModularInteger + From<BigUint>>() -> Vec<T> {
+    let m = T::modulus();
+    let mut samples = vec![T::zero(), T::one()];
+    for x in &[BigUint::from(2u32), BigUint::from(3u32), &m >> 1, &m - 2u32, &m - 1u32] {
+        if x < &m {
+            samples.push(x.clone().into());
+        }
+    }
+    samples.push((T::one() + T::one() + T::one()).pow(97));
+    samples
+}
+
+fn check_laws<T: ModularInteger + Debug>(a: T, b: T, c: T) {
+    let (zero, one) = (T::zero(), T::one());
+    assert_eq!((a + b) + c, a + (b + c), "associativity of + on {:?}, {:?}, {:?}", a, b, c);
+    assert_eq!((a * b) * c, a * (b * c), "associativity of * on {:?}, {:?}, {:?}", a, b, c);
+    assert_eq!(a + b, b + a, "commutativity of + on {:?}, {:?}", a, b);
+    assert_eq!(a * b, b * a, "commutativity of * on {:?}, {:?}", a, b);
+    assert_eq!(a * (b + c), a * b + a * c, "distributivity on {:?}, {:?}, {:?}", a, b, c);
+    assert_eq!(a + zero, a, "additive identity on {:?}", a);
+    assert_eq!(a * one, a, "multiplicative identity on {:?}", a);
+    assert_eq!(a * zero, zero, "absorption by zero on {:?}", a);
+    assert_eq!(a + (zero - a), zero, "additive inverse on {:?}", a);
+    assert_eq!((a - b) + b, a, "subtraction on {:?}, {:?}", a, b);
+    assert_eq!(a.pow(3), a * a * a, "exponentiation on {:?}", a);
+}
+
+/// Checks the commutative ring laws (associativity, commutativity, distributivity, identities,
+/// additive inverses) on all the triples of a set of edge-case values of the type.
+///
+/// **Warning**: panics at the first law that does not hold.
+pub fn assert_ring_axioms<T: ModularInteger + From<BigUint> + Debug>() {
+    let samples = edge_samples::<T>();
+    for &a in &samples {
+        for &b in &samples {
+            for &c in &samples {
+                check_laws(a, b, c);
+            }
+        }
+    }
+}
+
+/// Checks the ring laws and the existence of multiplicative inverses on `ROUNDS` triples drawn
+/// from `rng`, as well as on the edge-case values of the type. This fails if the modulus is not
+/// prime.
+///
+/// **Warning**: panics at the first law that does not hold.
+pub fn assert_field_axioms<F, R>(mut rng: R)
+where
+    F: ModularInteger + From<BigUint> + Debug,
+    R: FnMut() -> F,
+{
+    let check_inverse = |a: F| {
+        if a != F::zero() {
+            assert_eq!(a * a.inv(), F::one(), "multiplicative inverse of {:?}", a);
+        }
+    };
+    assert_ring_axioms::<F>();
+    edge_samples::<F>().into_iter().for_each(check_inverse);
+    for _ in 0..ROUNDS {
+        let (a, b, c) = (rng(), rng(), rng());
+        check_laws(a, b, c);
+        check_inverse(a);
+    }
+}
@@ -9,7 +9,11 @@
 //!
 //! Here is the macro used to defined the `SizeNatExample` type of this crate:
 //!
-//! ```ignore
+//! ```
+//! # use abstract_integers::*;
+//! # use num::{BigUint, CheckedSub, Zero};
+//! # use std::num::ParseIntError;
+//! # use std::ops::*;
 //! define_abstract_integer_checked!(SizeNatExample, 64);
 //! ```
 //!
@@ -27,7 +31,12 @@
 //! implement modular arithmetic. For instance, this crate defines the arithmetic field over the
 //! 9th Mersenne prime with:
 //!
-//! ```ignore
+//! ```
+//! # use abstract_integers::*;
+//! # use num::{BigUint, CheckedSub, Zero};
+//! # use std::num::ParseIntError;
+//! # use std::ops::*;
+//! # define_abstract_integer_checked!(SizeNatExample, 64);
 //! define_refined_modular_integer!(
 //!    SizeNatFieldExample,
 //!    SizeNatExample,
@@ -39,6 +48,9 @@
 //! argument is the name of the base abstract integer that will act as the representation. The
 //! third example is the modulo for all operations, defined as a value of the base type.
 //!
+//! Moduli of special shapes are detected on first use and reduced without division: this
+//! Mersenne modulus is reduced by adding the bits above the 61st to the low bits, see the
+//! `reduction` module.
 //!
 //! # Example
 //!
@@ -54,355 +66,3909 @@
 //! assert_eq!(SizeNatExample::from_literal(683313401270031), x5.into());
 //! let x6 = x5 / SizeNatExample::from_literal(1541654268);
 //! assert_eq!(SizeNatExample::from_literal(443233), x6.into());
-//! let x7 : SizeNatFieldExample = SizeNatFieldExample::from_literal(2305843009213693951) + x6.into();
-//! assert_eq!(x7, x6.into());
+//! let x7 : SizeNatFieldExample = SizeNatFieldExample::from_literal(2305843009213693950) + x6.into();
+//! let expected: SizeNatFieldExample = (x6 - SizeNatExample::from_literal(1)).into();
+//! assert_eq!(x7, expected);
 //! ```
 //!
 
 extern crate num;
+
+pub mod accumulator;
+pub mod any;
+pub mod byte_seq;
+#[cfg(feature = "cbor")]
+pub mod cbor;
+pub mod diagnostics;
+pub mod division;
+pub mod encoding;
+pub mod exponentiation;
+pub mod fuzzing;
+pub mod index;
+pub mod ladder;
+pub mod laws;
+pub mod number_theory;
+pub mod polynomial;
+pub mod promotion;
+pub mod ratio;
+pub mod recoding;
+pub mod reduction;
+#[cfg(feature = "registry")]
+pub mod registry;
+#[cfg(feature = "rfc6979")]
+pub mod rfc6979;
+#[cfg(feature = "schemars")]
+pub mod schema;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod shadow;
+pub mod tables;
+#[cfg(feature = "timing")]
+pub mod timing;
+pub mod traits;
+pub mod unbounded;
+pub mod vartime;
+#[cfg(feature = "xof")]
+pub mod xof;
+
+pub use accumulator::Accumulator;
+pub use any::{AnyAbstractInt, AnyInt};
+pub use byte_seq::ByteSeq;
+pub use division::DivisorContext;
+pub use exponentiation::{multi_pow, powers, PowTable};
+pub use index::{ArrayIndexExt, BoundedIndex};
+pub use promotion::Promoting;
+pub use ratio::Ratio;
+
+#[cfg(any(feature = "xof", feature = "rfc6979"))]
+#[doc(hidden)]
+pub use digest;
+#[doc(hidden)]
+pub use paste;
+#[cfg(feature = "generic-array")]
+#[doc(hidden)]
+pub use generic_array;
+#[cfg(feature = "registry")]
+#[doc(hidden)]
+pub use inventory;
+#[cfg(feature = "prost")]
+#[doc(hidden)]
+pub use prost;
+#[cfg(feature = "rand")]
+#[doc(hidden)]
+pub use rand;
+#[cfg(feature = "rlp")]
+#[doc(hidden)]
+pub use rlp;
+#[cfg(feature = "schemars")]
+#[doc(hidden)]
+pub use schemars;
+#[cfg(feature = "generic-array")]
+#[doc(hidden)]
+pub use typenum;
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde;
+#[cfg(feature = "ssz")]
+#[doc(hidden)]
+pub use ssz;
+#[cfg(feature = "subtle")]
+#[doc(hidden)]
+pub use subtle;
+pub use traits::{
+    AbstractInteger, CheckedInteger, DoubleWidth, Exponent, MachineInteger, ModularInteger,
+};
+pub use unbounded::UnboundedNat;
+pub use vartime::{VarTime, VarTimeModularOps, VarTimeOps};
+
 #[allow(unused_imports)]
 use num::{BigUint, CheckedSub, Zero};
 use std::num::ParseIntError;
 use std::ops::*;
 
-/// Defines a bounded natural integer with regular arithmetic operations, checked for overflow
-/// and underflow.
+/// Formats `x` for an error message, or `<redacted>` if it is secret.
+#[doc(hidden)]
+pub fn __redacted<T: std::fmt::Display>(x: T, secret: bool) -> String {
+    if secret {
+        String::from("<redacted>")
+    } else {
+        x.to_string()
+    }
+}
+
+/// Checks and queries the options passed after the mandatory arguments of the macros.
+#[doc(hidden)]
 #[macro_export]
-macro_rules! define_abstract_integer_checked {
-    ($name:ident, $bits:literal) => {
-        #[derive(Clone, Copy)]
-        pub struct $name([u8; ($bits + 7) / 8]);
+macro_rules! __abstract_integer_options {
+    (@check) => {};
+    (@check little_endian $($rest:ident)*) => {
+        $crate::__abstract_integer_options!(@check $($rest)*);
+    };
+    (@check big_endian $($rest:ident)*) => {
+        $crate::__abstract_integer_options!(@check $($rest)*);
+    };
+    (@check wide $($rest:ident)*) => {
+        $crate::__abstract_integer_options!(@check $($rest)*);
+    };
+    (@check secret $($rest:ident)*) => {
+        $crate::__abstract_integer_options!(@check $($rest)*);
+    };
+    (@check field_div $($rest:ident)*) => {
+        $crate::__abstract_integer_options!(@check $($rest)*);
+    };
+    (@check tables $($rest:ident)*) => {
+        $crate::__abstract_integer_options!(@check $($rest)*);
+    };
+    (@check no_div $($rest:ident)*) => {
+        $crate::__abstract_integer_options!(@check $($rest)*);
+    };
+    (@check no_ord $($rest:ident)*) => {
+        $crate::__abstract_integer_options!(@check $($rest)*);
+    };
+    (@check $other:ident $($rest:ident)*) => {
+        compile_error!(concat!("unknown abstract integer option `", stringify!($other), "`"));
+    };
+    (@has little_endian; little_endian $($rest:ident)*) => {
+        true
+    };
+    (@has secret; secret $($rest:ident)*) => {
+        true
+    };
+    (@has field_div; field_div $($rest:ident)*) => {
+        true
+    };
+    (@has tables; tables $($rest:ident)*) => {
+        true
+    };
+    (@has $flag:ident; $other:ident $($rest:ident)*) => {
+        $crate::__abstract_integer_options!(@has $flag; $($rest)*)
+    };
+    (@has $flag:ident;) => {
+        false
+    };
+    (@if wide; wide $($rest:ident)*; { $($items:tt)* }) => {
+        $($items)*
+    };
+    (@if secret; secret $($rest:ident)*; { $($items:tt)* }) => {
+        $($items)*
+    };
+    (@if no_ord; no_ord $($rest:ident)*; { $($items:tt)* }) => {
+        $($items)*
+    };
+    (@if field_div; field_div $($rest:ident)*; { $($items:tt)* }) => {
+        $($items)*
+    };
+    (@if tables; tables $($rest:ident)*; { $($items:tt)* }) => {
+        $($items)*
+    };
+    (@if $flag:ident; $other:ident $($rest:ident)*; { $($items:tt)* }) => {
+        $crate::__abstract_integer_options!(@if $flag; $($rest)*; { $($items)* });
+    };
+    (@if $flag:ident; ; { $($items:tt)* }) => {};
+    (@unless secret; secret $($rest:ident)*; { $($items:tt)* }) => {};
+    (@unless no_div; no_div $($rest:ident)*; { $($items:tt)* }) => {};
+    (@unless no_ord; no_ord $($rest:ident)*; { $($items:tt)* }) => {};
+    (@unless $flag:ident; $other:ident $($rest:ident)*; { $($items:tt)* }) => {
+        $crate::__abstract_integer_options!(@unless $flag; $($rest)*; { $($items)* });
+    };
+    (@unless $flag:ident; ; { $($items:tt)* }) => {
+        $($items)*
+    };
+}
 
-        impl Default for $name {
-            fn default() -> $name {
-                $name([0u8; ($bits + 7) / 8])
-            }
-        }
+/// Checks the options of `define_machine_integer`, which only supports `secret`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_machine_options {
+    () => {};
+    (secret $($rest:ident)*) => {
+        $crate::__abstract_integer_machine_options!($($rest)*);
+    };
+    ($other:ident $($rest:ident)*) => {
+        compile_error!(concat!("unknown machine integer option `", stringify!($other), "`"));
+    };
+}
 
-        impl From<BigUint> for $name {
-            fn from(x: BigUint) -> $name {
-                let max_value = BigUint::from(1u32) <<  $bits;
-                assert!(x < max_value, "BigUint {} is too big x for type {}!", x, stringify!($name));
-                let repr = x.to_bytes_be();
-                if repr.len() > ($bits + 7) / 8 {
-                    panic!("BigUint {} too big for type {}", x, stringify!($name))
-                }
-                let mut out = [0u8; ($bits + 7) / 8];
-                let upper = out.len();
-                let lower = upper - repr.len();
-                out[lower..upper].copy_from_slice(&repr);
-                $name(out)
+/// Implements `any::AnyAbstractInt`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_any {
+    ($name:ident) => {
+        impl $crate::any::AnyAbstractInt for $name {
+            fn type_name(&self) -> &'static str {
+                stringify!($name)
             }
-        }
 
-        impl Into<BigUint> for $name {
-            fn into(self) -> BigUint {
-                BigUint::from_bytes_be(&self.0)
+            fn to_biguint(&self) -> BigUint {
+                (*self).into()
             }
-        }
 
-        impl std::fmt::Display for $name {
-            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                let uint: BigUint = (*self).into();
-                write!(f, "{}", uint)
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
             }
-        }
 
-        impl std::fmt::Debug for $name {
-            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                let uint: BigUint = (*self).into();
-                write!(f, "{}", uint)
+            fn clone_box(&self) -> Box<dyn $crate::any::AnyAbstractInt> {
+                Box::new(*self)
             }
         }
+    };
+}
 
-        impl $name {
-            fn max() -> BigUint {
-                BigUint::from(2u32).shl($bits)
-            }
+/// Implements `From` for primitive unsigned integers, going through the conversion from `BigUint`.
+/// The `@try` variant implements `TryFrom` instead, for the checked types where the value might
+/// not fit.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_from_primitives {
+    (@try $name:ident, $($t:ty)*) => {
+        $(
+            impl std::convert::TryFrom<$t> for $name {
+                type Error = $crate::diagnostics::OutOfRangeError;
 
-            fn hex_string_to_bytes(s: &str) -> Vec<u8> {
-                assert!(s.len() % 2 == 0, "length of hex string {}: {}",s, s.len());
-                let b: Result<Vec<u8>, ParseIntError> = (0..s.len())
-                    .step_by(2)
-                    .map(|i| u8::from_str_radix(&s[i..i + 2], 16))
-                    .collect();
-                b.expect("Error parsing hex string")
+                fn try_from(x: $t) -> Result<$name, Self::Error> {
+                    let value = BigUint::from(x);
+                    if value > $name::max() {
+                        Err($crate::diagnostics::OutOfRangeError::new(stringify!($name), value)
+                            .secret($name::SECRET))
+                    } else {
+                        Ok(value.into())
+                    }
+                }
             }
-
-            #[allow(dead_code)]
-            pub fn from_hex(s: &str) -> Self {
-                BigUint::from_bytes_be(&Self::hex_string_to_bytes(s)).into()
+        )*
+    };
+    ($name:ident, $($t:ty)*) => {
+        $(
+            /// The value is reduced modulo the modulus for refined types, and wrapped for machine
+            /// integers.
+            impl From<$t> for $name {
+                fn from(x: $t) -> $name {
+                    BigUint::from(x).into()
+                }
             }
+        )*
+    };
+}
 
-            #[allow(dead_code)]
-            pub fn from_bytes_le(v: &[u8]) -> Self {
-                BigUint::from_bytes_le(v).into()
-            }
+/// Implements the serde traits through `serialization::SerdeInteger` when the `serde` feature
+/// is enabled, expands to nothing otherwise.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_serde {
+    ($name:ident, $bytes:expr, |$x:ident| $try_from:expr) => {
+        impl $crate::serialization::SerdeInteger for $name {
+            const TYPE_NAME: &'static str = stringify!($name);
+            const BYTES: usize = $bytes;
+            const SECRET: bool = $name::SECRET;
 
-            #[allow(dead_code)]
-            pub fn to_bytes_le(self) -> Vec<u8> {
-                BigUint::to_bytes_le(&self.into())
+            fn to_biguint(&self) -> BigUint {
+                (*self).into()
             }
 
-            #[allow(dead_code)]
-            pub fn from_literal(x: u128) -> Self {
-                let big_x = BigUint::from(x);
-                if big_x > $name::max().into() {
-                    panic!("literal {} too big for type {}", x, stringify!($name));
-                }
-                big_x.into()
+            fn try_from_biguint($x: BigUint) -> Option<Self> {
+                $try_from
             }
+        }
 
-            #[allow(dead_code)]
-            pub fn from_signed_literal(x: i128) -> Self {
-                let big_x = BigUint::from(x as u128);
-                if big_x > $name::max().into() {
-                    panic!("literal {} too big for type {}", x, stringify!($name));
-                }
-                big_x.into()
+        impl $crate::serde::Serialize for $name {
+            fn serialize<S: $crate::serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                $crate::serialization::serialize(self, s)
             }
         }
 
-        /// **Warning**: panics on overflow.
-        impl Add for $name {
-            type Output = $name;
-            fn add(self, rhs: $name) -> $name {
-                let a: BigUint = self.into();
-                let b: BigUint = rhs.into();
-                let c = a + b;
-                if c > $name::max() {
-                    panic!("bounded addition overflow for type {}", stringify!($name));
-                }
-                c.into()
+        impl<'de> $crate::serde::Deserialize<'de> for $name {
+            fn deserialize<D: $crate::serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                $crate::serialization::deserialize(d)
             }
         }
+    };
+}
 
-        /// **Warning**: panics on underflow.
-        impl Sub for $name {
-            type Output = $name;
-            fn sub(self, rhs: $name) -> $name {
-                let a: BigUint = self.into();
-                let b: BigUint = rhs.into();
-                let c = a.checked_sub(&b).unwrap_or_else(|| {
-                    panic!(
-                        "bounded substraction underflow for type {}",
-                        stringify!($name)
-                    )
-                });
-                c.into()
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_serde {
+    ($($args:tt)*) => {};
+}
+
+/// Implements `JsonSchema` when the `schemars` feature is enabled, expands to nothing otherwise.
+/// `$max` is the largest value of the type, as a `BigUint`.
+#[cfg(feature = "schemars")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_schema {
+    ($name:ident, $max:expr) => {
+        impl $crate::schemars::JsonSchema for $name {
+            fn schema_name() -> std::borrow::Cow<'static, str> {
+                stringify!($name).into()
             }
-        }
 
-        /// **Warning**: panics on overflow.
-        impl Mul for $name {
-            type Output = $name;
-            fn mul(self, rhs: $name) -> $name {
-                let a: BigUint = self.into();
-                let b: BigUint = rhs.into();
-                let c = a * b;
-                if c > $name::max() {
-                    panic!(
-                        "bounded multiplication overflow for type {}",
-                        stringify!($name)
-                    );
-                }
-                c.into()
+            fn schema_id() -> std::borrow::Cow<'static, str> {
+                concat!(module_path!(), "::", stringify!($name)).into()
             }
-        }
 
-        /// **Warning**: panics on division by 0.
-        impl Div for $name {
-            type Output = $name;
-            fn div(self, rhs: $name) -> $name {
-                let a: BigUint = self.into();
-                let b: BigUint = rhs.into();
-                if b == BigUint::zero() {
-                    panic!("dividing by zero in type {}", stringify!($name));
-                }
-                let c = a / b;
-                c.into()
+            fn json_schema(_: &mut $crate::schemars::SchemaGenerator) -> $crate::schemars::Schema {
+                $crate::schema::hex_schema(
+                    stringify!($name),
+                    <$name as $crate::serialization::SerdeInteger>::BYTES,
+                    &$max,
+                )
             }
         }
+    };
+}
 
-        /// **Warning**: panics on division by 0.
-        impl Rem for $name {
-            type Output = $name;
-            fn rem(self, rhs: $name) -> $name {
-                let a: BigUint = self.into();
-                let b: BigUint = rhs.into();
-                if b == BigUint::zero() {
-                    panic!("dividing by zero in type {}", stringify!($name));
-                }
-                let c = a % b;
-                c.into()
+#[cfg(not(feature = "schemars"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_schema {
+    ($($args:tt)*) => {};
+}
+
+/// Implements `subtle::ConstantTimeEq` and an inherent `ct_eq` method when the `subtle` feature
+/// is enabled, expands to nothing otherwise. `$eq` compares the representations of `$a` and
+/// `$b` in constant time.
+#[cfg(feature = "subtle")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_ct_eq {
+    ($name:ident, |$a:ident, $b:ident| $eq:expr) => {
+        impl $crate::subtle::ConstantTimeEq for $name {
+            fn ct_eq(&self, other: &$name) -> $crate::subtle::Choice {
+                let ($a, $b) = (self, other);
+                $eq
             }
         }
 
-        impl PartialEq for $name {
-            fn eq(&self, rhs: &$name) -> bool {
-                let a: BigUint = (*self).into();
-                let b: BigUint = (*rhs).into();
-                a == b
+        impl $name {
+            /// Compares the values without branching on them, for instance to check MAC tags.
+            #[allow(dead_code)]
+            pub fn ct_eq(&self, other: &$name) -> $crate::subtle::Choice {
+                $crate::subtle::ConstantTimeEq::ct_eq(self, other)
             }
         }
+    };
+}
 
-        impl Eq for $name {}
+#[cfg(not(feature = "subtle"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_ct_eq {
+    ($($args:tt)*) => {};
+}
 
-        impl PartialOrd for $name {
-            fn partial_cmp(&self, other: &$name) -> Option<std::cmp::Ordering> {
-                let a: BigUint = (*self).into();
-                let b: BigUint = (*other).into();
-                a.partial_cmp(&b)
+/// Implements `to_cbor` and `from_cbor` when the `cbor` feature is enabled, expands to nothing
+/// otherwise, see `cbor`. `$try_from` rejects the values out of the range of the type by
+/// returning `None`.
+#[cfg(feature = "cbor")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_cbor {
+    ($name:ident, |$x:ident| $try_from:expr) => {
+        impl $name {
+            /// Returns the canonical CBOR encoding of the value, an unsigned integer or a
+            /// positive bignum.
+            #[allow(dead_code)]
+            pub fn to_cbor(self) -> Vec<u8> {
+                $crate::cbor::encode(&self.into())
             }
-        }
 
-        impl Ord for $name {
-            fn cmp(&self, other: &$name) -> std::cmp::Ordering {
-                self.partial_cmp(other).unwrap()
+            /// Decodes a canonical CBOR integer, rejecting non-canonical encodings and values
+            /// out of the range of the type.
+            #[allow(dead_code)]
+            pub fn from_cbor(input: &[u8]) -> Result<Self, $crate::cbor::CborError> {
+                let value = $crate::cbor::decode(input)?;
+                let $x = value.clone();
+                let x: Option<$name> = $try_from;
+                x.ok_or($crate::cbor::CborError::OutOfRange(
+                    $crate::diagnostics::OutOfRangeError::new(stringify!($name), value)
+                        .secret($name::SECRET),
+                ))
             }
         }
+    };
+}
 
-        impl $name {
-            /// Returns 2 to the power of the argument
-            #[allow(dead_code)]
-            pub fn pow2(x: usize) -> $name {
-                BigUint::from(1u32).shl(x).into()
+#[cfg(not(feature = "cbor"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_cbor {
+    ($($args:tt)*) => {};
+}
+
+/// Registers the type in `registry` when the `registry` feature is enabled, expands to nothing
+/// otherwise.
+#[cfg(feature = "registry")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_register {
+    ($name:ident, $bits:expr, $base:expr, $modulus:expr) => {
+        $crate::inventory::submit! {
+            $crate::registry::IntegerType {
+                name: stringify!($name),
+                module_path: module_path!(),
+                bits: $bits,
+                base: $base,
+                __modulus: $modulus,
             }
+        }
+    };
+}
 
-            /// Gets the `i`-th least significant bit of this integer.
+#[cfg(not(feature = "registry"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_register {
+    ($($args:tt)*) => {};
+}
+
+/// Implements `random_prime` and `is_probable_prime` when the `rand` feature is enabled, expands
+/// to nothing otherwise.
+#[cfg(feature = "rand")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_rand {
+    ($name:ident) => {
+        impl $name {
+            /// Returns a random prime of exactly `bits` bits, see `number_theory::random_prime`.
+            ///
+            /// **Warning**: panics if `bits` is below 2 or above the bits of the type.
             #[allow(dead_code)]
-            pub fn bit(self, i: usize) -> bool {
+            pub fn random_prime<R>(bits: usize, rng: &mut R) -> Self
+            where
+                R: $crate::rand::RngCore + ?Sized,
+            {
                 assert!(
-                    i < self.0.len() * 8,
-                    "the bit queried should be lower than the size of the integer representation: {} < {}",
-                    i,
-                    self.0.len() * 8
+                    bits <= $name::BITS,
+                    "cannot generate a {}-bit prime in type {}",
+                    bits,
+                    stringify!($name)
                 );
-                let bigint : BigUint = self.into();
-                let tmp: BigUint = bigint >> i;
-                (tmp & BigUint::from(1u128)).to_bytes_le()[0] == 1
+                $crate::number_theory::random_prime(bits, rng).into()
             }
 
+            /// Miller-Rabin primality test with `rounds` random bases, see
+            /// `number_theory::is_probable_prime`.
             #[allow(dead_code)]
-            pub fn inv(self, modval: Self) -> Self {
-                let biguintmodval : BigUint = modval.into();
-                let m = &biguintmodval - BigUint::from(2u32);
-                let s: BigUint = (self).into();
-                s.modpow(&m, &biguintmodval).into()
+            pub fn is_probable_prime<R>(self, rounds: usize, rng: &mut R) -> bool
+            where
+                R: $crate::rand::RngCore + ?Sized,
+            {
+                $crate::number_theory::is_probable_prime(&self.into(), rounds, rng)
             }
+        }
+    };
+}
+
+#[cfg(not(feature = "rand"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_rand {
+    ($($args:tt)*) => {};
+}
 
+/// Implements `derive_from_seed` on refined types when the `xof` feature is enabled, expands to
+/// nothing otherwise.
+#[cfg(feature = "xof")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_xof {
+    ($name:ident) => {
+        impl $name {
+            /// Returns an element uniformly distributed modulo the modulus, derived
+            /// deterministically from an XOF that has absorbed the seed and from the domain,
+            /// see `xof`.
             #[allow(dead_code)]
-            pub fn pow_felem(self, exp: Self, modval: Self) -> Self {
-                let a: BigUint = self.into();
-                let b: BigUint = exp.into();
-                let m: BigUint = modval.into();
-                let c: BigUint = a.modpow(&b, &m);
-                c.into()
+            pub fn derive_from_seed<X>(xof: X, domain: &[u8]) -> Self
+            where
+                X: $crate::digest::Update + $crate::digest::ExtendableOutput,
+            {
+                $crate::xof::derive_below(xof, domain, &$name::max().into()).into()
             }
-            /// Returns self to the power of the argument.
-            /// The exponent is a u128.
+        }
+    };
+}
+
+#[cfg(not(feature = "xof"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_xof {
+    ($($args:tt)*) => {};
+}
+
+/// Implements `rfc6979_nonce` on refined types when the `rfc6979` feature is enabled, expands
+/// to nothing otherwise.
+#[cfg(feature = "rfc6979")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_rfc6979 {
+    ($name:ident) => {
+        impl $name {
+            /// Returns the RFC 6979 nonce in `[1, q-1]` for the private key and the hash of the
+            /// message, with `q` the modulus and `M` the HMAC of the hash function, see
+            /// `rfc6979`.
+            ///
+            /// **Warning**: panics if the modulus is below 2.
             #[allow(dead_code)]
-            pub fn pow(self, exp: u128, modval: Self) -> Self {
-                self.pow_felem(BigUint::from(exp).into(), modval)
+            pub fn rfc6979_nonce<M>(private_key: Self, hash: &[u8]) -> Self
+            where
+                M: $crate::digest::Mac + $crate::digest::KeyInit,
+            {
+                let q: BigUint = $name::max().into();
+                $crate::rfc6979::generate_k::<M>(&private_key.into(), &q, hash).into()
             }
         }
     };
 }
 
-/// Defines a bounded natural integer with modular arithmetic operations
+#[cfg(not(feature = "rfc6979"))]
+#[doc(hidden)]
 #[macro_export]
-macro_rules! define_refined_modular_integer {
-    ($name:ident, $base:ident, $max:expr) => {
-        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
-        pub struct $name($base);
+macro_rules! __abstract_integer_rfc6979 {
+    ($($args:tt)*) => {};
+}
 
-        impl std::fmt::Display for $name {
-            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                let uint: $base = (*self).into();
-                write!(f, "{}", uint)
+/// Implements the conversions to and from the `Vec<u8>` and `Bytes` values of protobuf `bytes`
+/// fields when the `prost` feature is enabled, expands to nothing otherwise. Values are encoded
+/// in minimal big-endian form. `$try_from` rejects the values out of the range of the type by
+/// returning `None`, leading zeros are accepted on decode.
+#[cfg(feature = "prost")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_prost {
+    ($name:ident, |$x:ident| $try_from:expr) => {
+        impl From<$name> for Vec<u8> {
+            fn from(x: $name) -> Vec<u8> {
+                x.to_bytes_minimal()
             }
         }
 
-        impl std::fmt::Debug for $name {
-            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                let uint: $base = (*self).into();
-                write!(f, "{}", uint)
+        impl From<$name> for $crate::prost::bytes::Bytes {
+            fn from(x: $name) -> $crate::prost::bytes::Bytes {
+                x.to_bytes_minimal().into()
             }
         }
 
+        impl std::convert::TryFrom<&[u8]> for $name {
+            type Error = $crate::diagnostics::OutOfRangeError;
+
+            fn try_from(v: &[u8]) -> Result<$name, Self::Error> {
+                let value = BigUint::from_bytes_be(v);
+                let $x = value.clone();
+                let x: Option<$name> = $try_from;
+                x.ok_or(
+                    $crate::diagnostics::OutOfRangeError::new(stringify!($name), value)
+                        .secret($name::SECRET),
+                )
+            }
+        }
+
+        impl std::convert::TryFrom<Vec<u8>> for $name {
+            type Error = $crate::diagnostics::OutOfRangeError;
+
+            fn try_from(v: Vec<u8>) -> Result<$name, Self::Error> {
+                std::convert::TryFrom::try_from(&v[..])
+            }
+        }
+
+        impl std::convert::TryFrom<$crate::prost::bytes::Bytes> for $name {
+            type Error = $crate::diagnostics::OutOfRangeError;
+
+            fn try_from(v: $crate::prost::bytes::Bytes) -> Result<$name, Self::Error> {
+                std::convert::TryFrom::try_from(&v[..])
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "prost"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_prost {
+    ($($args:tt)*) => {};
+}
+
+/// Implements the `rlp` traits when the `rlp` feature is enabled, expands to nothing
+/// otherwise. Values are encoded as RLP integers, with their minimal big-endian encoding.
+/// Decoding rejects leading zeros, and `$try_from` rejects the values out of the range of the type
+/// by returning `None`.
+#[cfg(feature = "rlp")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_rlp {
+    ($name:ident, |$x:ident| $try_from:expr) => {
+        impl $crate::rlp::Encodable for $name {
+            fn rlp_append(&self, s: &mut $crate::rlp::RlpStream) {
+                s.encoder().encode_value(&self.to_bytes_minimal());
+            }
+        }
+
+        impl $crate::rlp::Decodable for $name {
+            fn decode(rlp: &$crate::rlp::Rlp) -> Result<Self, $crate::rlp::DecoderError> {
+                rlp.decoder().decode_value(|bytes| {
+                    if bytes.first() == Some(&0) {
+                        return Err($crate::rlp::DecoderError::RlpInvalidIndirection);
+                    }
+                    let $x = BigUint::from_bytes_be(bytes);
+                    let value: Option<$name> = $try_from;
+                    value.ok_or($crate::rlp::DecoderError::Custom(concat!(
+                        "value out of the range of type ",
+                        stringify!($name)
+                    )))
+                })
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "rlp"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_rlp {
+    ($($args:tt)*) => {};
+}
+
+/// Implements the SSZ `Encode` and `Decode` traits when the `ssz` feature is enabled, expands
+/// to nothing otherwise. Values are encoded as fixed-length little-endian integers of the byte
+/// width of the type, which is the SSZ `uintN` for the standard widths. Decoding rejects the
+/// values above the bound of the type.
+#[cfg(feature = "ssz")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_ssz {
+    ($name:ident) => {
+        impl $crate::ssz::Encode for $name {
+            fn is_ssz_fixed_len() -> bool {
+                true
+            }
+
+            fn ssz_fixed_len() -> usize {
+                $name::BYTES
+            }
+
+            fn ssz_bytes_len(&self) -> usize {
+                $name::BYTES
+            }
+
+            fn ssz_append(&self, buf: &mut Vec<u8>) {
+                let mut le_bytes = self.into_bytes();
+                if !$name::LITTLE_ENDIAN {
+                    le_bytes.reverse();
+                }
+                buf.extend_from_slice(&le_bytes);
+            }
+        }
+
+        impl $crate::ssz::Decode for $name {
+            fn is_ssz_fixed_len() -> bool {
+                true
+            }
+
+            fn ssz_fixed_len() -> usize {
+                $name::BYTES
+            }
+
+            fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, $crate::ssz::DecodeError> {
+                if bytes.len() != $name::BYTES {
+                    return Err($crate::ssz::DecodeError::InvalidByteLength {
+                        len: bytes.len(),
+                        expected: $name::BYTES,
+                    });
+                }
+                let x = BigUint::from_bytes_le(bytes);
+                if x > $name::max() {
+                    return Err($crate::ssz::DecodeError::BytesInvalid(format!(
+                        "value too big for type {}",
+                        stringify!($name)
+                    )));
+                }
+                Ok(x.into())
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "ssz"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_ssz {
+    ($($args:tt)*) => {};
+}
+
+/// Number of bits of the value written in hexadecimal in `s`, with an optional `0x` prefix.
+/// Used to size the base type of `define_curve_fields` at compile time.
+#[doc(hidden)]
+pub const fn __hex_bits(s: &str) -> usize {
+    let s = s.as_bytes();
+    let mut i = if s.len() >= 2 && s[0] == b'0' && (s[1] == b'x' || s[1] == b'X') {
+        2
+    } else {
+        0
+    };
+    while i < s.len() && s[i] == b'0' {
+        i += 1;
+    }
+    if i == s.len() {
+        return 0;
+    }
+    let first = match s[i] {
+        b'0'..=b'9' => s[i] - b'0',
+        b'a'..=b'f' => s[i] - b'a' + 10,
+        b'A'..=b'F' => s[i] - b'A' + 10,
+        _ => panic!("invalid hex digit"),
+    };
+    4 * (s.len() - i) - (first.leading_zeros() as usize - 4)
+}
+
+/// Radix and offset of the first digit of an integer literal as written in Rust source, with
+/// an optional `0x`, `0o` or `0b` prefix.
+const fn literal_radix(s: &[u8]) -> (u32, usize) {
+    if s.len() >= 2 && s[0] == b'0' {
+        match s[1] {
+            b'x' | b'X' => return (16, 2),
+            b'o' | b'O' => return (8, 2),
+            b'b' | b'B' => return (2, 2),
+            _ => (),
+        }
+    }
+    (10, 0)
+}
+
+/// Value of a digit of a literal in the given radix.
+const fn literal_digit(c: u8, radix: u32) -> u32 {
+    let d = match c {
+        b'0'..=b'9' => (c - b'0') as u32,
+        b'a'..=b'f' => (c - b'a') as u32 + 10,
+        b'A'..=b'F' => (c - b'A') as u32 + 10,
+        _ => panic!("invalid digit in integer literal, type suffixes are not allowed"),
+    };
+    if d >= radix {
+        panic!("invalid digit in integer literal, type suffixes are not allowed")
+    }
+    d
+}
+
+/// Number of bits of the value of an integer literal, with `_` separators and an optional
+/// radix prefix. Decimal literals must fit in a `u128`, larger values are written in
+/// hexadecimal. Used by `nat!` to check literals at compile time.
+#[doc(hidden)]
+pub const fn __literal_bits(s: &str) -> usize {
+    let s = s.as_bytes();
+    let (radix, mut i) = literal_radix(s);
+    if radix == 10 {
+        let mut x: u128 = 0;
+        while i < s.len() {
+            if s[i] != b'_' {
+                x = match x.checked_mul(10) {
+                    Some(x) => match x.checked_add(literal_digit(s[i], 10) as u128) {
+                        Some(x) => x,
+                        None => panic!("decimal literal too large, write it in hexadecimal"),
+                    },
+                    None => panic!("decimal literal too large, write it in hexadecimal"),
+                };
+            }
+            i += 1;
+        }
+        return 128 - x.leading_zeros() as usize;
+    }
+    let digit_bits = radix.trailing_zeros() as usize;
+    let mut bits = 0;
+    while i < s.len() {
+        if s[i] != b'_' {
+            let d = literal_digit(s[i], radix);
+            if bits > 0 {
+                bits += digit_bits;
+            } else if d != 0 {
+                bits = 32 - d.leading_zeros() as usize;
+            }
+        }
+        i += 1;
+    }
+    bits
+}
+
+/// Returns `true` if the integer literal, accepted by `__literal_bits`, is below `bound`, or if
+/// there is no bound. Used by `nat!` to check literals against the moduli known at compile time.
+#[doc(hidden)]
+pub const fn __literal_below(s: &str, bound: Option<u128>) -> bool {
+    let bound = match bound {
+        Some(bound) => bound,
+        None => return true,
+    };
+    if __literal_bits(s) > 128 {
+        return false;
+    }
+    let s = s.as_bytes();
+    let (radix, mut i) = literal_radix(s);
+    let mut x: u128 = 0;
+    while i < s.len() {
+        if s[i] != b'_' {
+            x = x * radix as u128 + literal_digit(s[i], radix) as u128;
+        }
+        i += 1;
+    }
+    x < bound
+}
+
+/// Parses an integer literal accepted by `__literal_bits`.
+#[doc(hidden)]
+pub fn __parse_literal(s: &str) -> BigUint {
+    let (radix, i) = literal_radix(s.as_bytes());
+    let digits: String = s[i..].chars().filter(|c| *c != '_').collect();
+    BigUint::parse_bytes(digits.as_bytes(), radix).expect("invalid integer literal")
+}
+
+/// Nearest `f64` to `x`, or infinity if `x` exceeds the range of `f64`.
+#[doc(hidden)]
+pub fn __biguint_to_f64(x: &BigUint) -> f64 {
+    num::ToPrimitive::to_f64(x).unwrap_or(f64::INFINITY)
+}
+
+/// Converts a float holding a natural integer, then checks it against `bound` (exclusive).
+#[doc(hidden)]
+pub fn __f64_to_biguint(
+    x: f64,
+    type_name: &'static str,
+    secret: bool,
+    bound: BigUint,
+) -> Result<BigUint, diagnostics::FromFloatError> {
+    use diagnostics::FromFloatError;
+    if !x.is_finite() {
+        return Err(FromFloatError::NotFinite);
+    }
+    if x < 0.0 {
+        return Err(FromFloatError::Negative);
+    }
+    if x.fract() != 0.0 {
+        return Err(FromFloatError::NotInteger);
+    }
+    let value = num::FromPrimitive::from_f64(x).expect("finite floats convert");
+    if value >= bound {
+        return Err(FromFloatError::OutOfRange(
+            diagnostics::OutOfRangeError::new(type_name, value).secret(secret),
+        ));
+    }
+    Ok(value)
+}
+
+/// Writes the decimal expansion of the number whose 64-bit limbs, least significant first, are
+/// `limbs`, without allocating. The limbs are consumed, and `chunks` must have room for the
+/// chunks of 19 decimal digits of the number.
+#[doc(hidden)]
+pub fn __write_decimal(
+    f: &mut std::fmt::Formatter,
+    limbs: &mut [u64],
+    chunks: &mut [u64],
+) -> std::fmt::Result {
+    const CHUNK: u128 = 10_000_000_000_000_000_000;
+    let mut n = 0;
+    while limbs.iter().any(|limb| *limb != 0) {
+        let mut rem = 0u128;
+        for limb in limbs.iter_mut().rev() {
+            let cur = (rem << 64) | *limb as u128;
+            *limb = (cur / CHUNK) as u64;
+            rem = cur % CHUNK;
+        }
+        chunks[n] = rem as u64;
+        n += 1;
+    }
+    match chunks[..n].split_last() {
+        None => write!(f, "0"),
+        Some((top, rest)) => {
+            write!(f, "{}", top)?;
+            rest.iter().rev().try_for_each(|chunk| write!(f, "{:019}", chunk))
+        }
+    }
+}
+
+#[doc(hidden)]
+pub const fn __max_bits(a: usize, b: usize) -> usize {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+/// Implements the conversions to and from `GenericArray` when the `generic-array` feature is
+/// enabled, expands to nothing otherwise. `$bytes` is the byte length of the encoding, and
+/// `$to`/`$from` convert to and from the canonical fixed-size byte array.
+#[cfg(feature = "generic-array")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_generic_array {
+    ($name:ident, $bytes:expr, |$x:ident| $to:expr, |$a:ident| $from:expr) => {
+        impl $name {
+            /// Returns the canonical byte encoding of the value with a type-level length, for
+            /// the APIs built on `generic_array`.
+            #[allow(dead_code)]
+            pub fn to_generic_array(
+                self,
+            ) -> $crate::generic_array::GenericArray<u8, $crate::typenum::U<{ $bytes }>> {
+                let $x = self;
+                $crate::generic_array::GenericArray::from($to)
+            }
+
+            /// Reads a value from its canonical byte encoding, see `to_generic_array`.
+            #[allow(dead_code)]
+            pub fn from_generic_array(
+                v: &$crate::generic_array::GenericArray<u8, $crate::typenum::U<{ $bytes }>>,
+            ) -> Self {
+                let $a: [u8; $bytes] = (*v).into();
+                $from
+            }
+        }
+
+        impl From<$name> for $crate::generic_array::GenericArray<u8, $crate::typenum::U<{ $bytes }>> {
+            fn from(x: $name) -> Self {
+                x.to_generic_array()
+            }
+        }
+
+        impl From<$crate::generic_array::GenericArray<u8, $crate::typenum::U<{ $bytes }>>> for $name {
+            fn from(v: $crate::generic_array::GenericArray<u8, $crate::typenum::U<{ $bytes }>>) -> Self {
+                $name::from_generic_array(&v)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "generic-array"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __abstract_integer_generic_array {
+    ($($args:tt)*) => {};
+}
+
+/// Defines a bounded natural integer with regular arithmetic operations, checked for overflow
+/// and underflow.
+///
+/// The canonical byte representation of the integer is big-endian. Adding the `little_endian`
+/// option after the number of bits makes it little-endian instead, which changes the layout
+/// returned by `as_bytes`/`into_bytes` and expected by `from_bytes`.
+///
+/// The `wide` option also defines a big-endian companion type of twice the number of bits,
+/// named after the type with a `Wide` suffix, and a `mul_wide` method computing the full product
+/// of two values in it: `define_abstract_integer_checked!(Felem, 256, wide)` defines `Felem` and
+/// `FelemWide`.
+///
+/// The `secret` option makes `Debug` and `Display` print `<redacted N-bit secret>` instead of
+/// the value, so that key material does not end up in logs. Refined types over a secret base
+/// are redacted as well. Division, remainder, `inv`, `pow` and `pow_felem`, which run in
+/// variable time, are then only available on the explicit view returned by `var_time()`, see
+/// `vartime`.
+///
+/// The `no_div` option removes the `/` and `%` operators, for specifications that do not define
+/// them.
+///
+/// The definition can be wrapped in a public module to avoid name collisions, with the items in
+/// scope at the call site visible inside the module:
+///
+/// ```
+/// # use abstract_integers::*;
+/// # use num::{BigUint, CheckedSub, Zero};
+/// # use std::num::ParseIntError;
+/// # use std::ops::*;
+/// define_abstract_integer_checked!(mod p256 { Nat, 512 });
+/// # fn main() {
+/// let x = p256::Nat::from_literal(1);
+/// # }
+/// ```
+///
+/// Attributes written before the name of the type, such as documentation or extra derives, are
+/// attached to the generated struct:
+///
+/// ```
+/// # use abstract_integers::*;
+/// # use num::{BigUint, CheckedSub, Zero};
+/// # use std::num::ParseIntError;
+/// # use std::ops::*;
+/// define_abstract_integer_checked!(#[derive(Hash)] MyNat, 64);
+/// # let _ = std::collections::HashSet::from([MyNat::from_literal(1)]);
+/// ```
+///
+/// Named constants can be given in a block after the options. They become associated constants
+/// of the type, and a constant too big for the type is a compile-time error:
+///
+/// ```
+/// # use abstract_integers::*;
+/// # use num::{BigUint, CheckedSub, Zero};
+/// # use std::num::ParseIntError;
+/// # use std::ops::*;
+/// define_abstract_integer_checked!(Nat, 256; constants { GENERATOR = 5, COFACTOR = 8 });
+/// let g = Nat::GENERATOR;
+/// # assert_eq!(g, Nat::from_literal(5));
+/// ```
+#[macro_export]
+macro_rules! define_abstract_integer_checked {
+    (mod $module:ident { $($args:tt)* }) => {
+        pub mod $module {
+            #[allow(unused_imports)]
+            use super::*;
+            $crate::define_abstract_integer_checked!($($args)*);
+        }
+    };
+    (
+        $(#[$attr:meta])* $name:ident, $bits:expr $(, $opt:ident)* $(,)?
+        $(; constants { $($cname:ident = $cval:expr),* $(,)? })?
+    ) => {
+        $crate::__abstract_integer_options!(@check $($opt)*);
+        $crate::__abstract_integer_options!(@if no_ord; $($opt)*; {
+            compile_error!("the `no_ord` option is only available for refined types");
+        });
+        $crate::__abstract_integer_options!(@if field_div; $($opt)*; {
+            compile_error!("the `field_div` option is only available for refined types");
+        });
+        $crate::__abstract_integer_options!(@if tables; $($opt)*; {
+            compile_error!("the `tables` option is only available for refined types");
+        });
+
+        $($(
+            impl $name {
+                pub const $cname: $name = $name::__from_u128_const($cval);
+            }
+
+            const _: $name = $name::$cname;
+        )*)?
+
+        $crate::__abstract_integer_options!(@if wide; $($opt)*; {
+            $crate::paste::paste! {
+                $crate::define_abstract_integer_checked!(
+                    #[doc = concat!("Double-width companion of `", stringify!($name), "`.")]
+                    [<$name Wide>],
+                    2 * $bits
+                );
+
+                impl $name {
+                    /// Returns the full product of self and the argument, which cannot
+                    /// overflow the double-width companion type.
+                    #[allow(dead_code)]
+                    pub fn mul_wide(self, rhs: $name) -> [<$name Wide>] {
+                        let a: BigUint = self.into();
+                        let b: BigUint = rhs.into();
+                        (a * b).into()
+                    }
+                }
+
+                $crate::define_double_width!([<$name Wide>], $name);
+            }
+        });
+
+        $(#[$attr])*
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        #[repr(transparent)]
+        pub struct $name([u8; ($bits as usize).div_ceil(8)]);
+
+        impl Default for $name {
+            fn default() -> $name {
+                $name([0u8; $name::BYTES])
+            }
+        }
+
+        impl From<BigUint> for $name {
+            fn from(x: BigUint) -> $name {
+                let max_value = BigUint::from(1u32) <<  $bits;
+                assert!(
+                    x < max_value,
+                    "BigUint {} is too big x for type {}!",
+                    $crate::__redacted(&x, $name::SECRET),
+                    stringify!($name)
+                );
+                let repr = x.to_bytes_be();
+                if repr.len() > $name::BYTES {
+                    panic!(
+                        "BigUint {} too big for type {}",
+                        $crate::__redacted(&x, $name::SECRET),
+                        stringify!($name)
+                    )
+                }
+                let mut out = [0u8; $name::BYTES];
+                let upper = out.len();
+                let lower = upper - repr.len();
+                out[lower..upper].copy_from_slice(&repr);
+                if $name::LITTLE_ENDIAN {
+                    out.reverse();
+                }
+                let out = $name(out);
+                out.check_invariant();
+                out
+            }
+        }
+
+        impl From<$name> for BigUint {
+            fn from(x: $name) -> BigUint {
+                x.check_invariant();
+                if $name::LITTLE_ENDIAN {
+                    BigUint::from_bytes_le(&x.0)
+                } else {
+                    BigUint::from_bytes_be(&x.0)
+                }
+            }
+        }
+
+        impl $crate::traits::Exponent for $name {}
+
+        impl $crate::traits::CheckedInteger for $name {
+            const BITS: usize = $bits;
+            const BYTES: usize = $name::BYTES;
+            const SECRET: bool = $name::SECRET;
+
+            fn max_value() -> BigUint {
+                $name::max()
+            }
+        }
+
+        impl $crate::traits::AbstractInteger for $name {
+            fn from_literal(x: u128) -> Self {
+                $name::from_literal(x)
+            }
+
+            fn bound() -> Option<BigUint> {
+                Some($name::max())
+            }
+        }
+
+        $crate::__abstract_integer_from_primitives!(@try $name, u8 u16 u32 u64 usize);
+        $crate::__abstract_integer_serde!($name, $name::BYTES, |x| {
+            if x <= $name::max() {
+                Some(x.into())
+            } else {
+                None
+            }
+        });
+        $crate::__abstract_integer_schema!($name, $name::max());
+        $crate::__abstract_integer_rlp!($name, |x| {
+            if x <= $name::max() {
+                Some(x.into())
+            } else {
+                None
+            }
+        });
+        $crate::__abstract_integer_cbor!($name, |x| {
+            if x <= $name::max() {
+                Some(x.into())
+            } else {
+                None
+            }
+        });
+        $crate::__abstract_integer_prost!($name, |x| {
+            if x <= $name::max() {
+                Some(x.into())
+            } else {
+                None
+            }
+        });
+        $crate::__abstract_integer_ct_eq!($name, |a, b| {
+            $crate::subtle::ConstantTimeEq::ct_eq(&a.0[..], &b.0[..])
+        });
+        $crate::__abstract_integer_ssz!($name);
+        $crate::__abstract_integer_rand!($name);
+        $crate::__abstract_integer_options!(@unless secret; $($opt)*; {
+            $crate::__abstract_integer_any!($name);
+        });
+        $crate::__abstract_integer_register!($name, $bits, None, None);
+        $crate::__abstract_integer_generic_array!(
+            $name,
+            $name::BYTES,
+            |x| x.into_bytes(),
+            |a| $name::from_bytes(&a)
+        );
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                if $name::SECRET {
+                    return write!(f, "<redacted {}-bit secret>", $bits);
+                }
+                let mut chunks = [0u64; 2 * $name::LIMBS + 1];
+                $crate::__write_decimal(f, &mut self.as_limbs(), &mut chunks)
+            }
+        }
+
+        /// With `{:#?}`, prints the type name, the bit width and the value in hexadecimal and
+        /// decimal, the value staying redacted for secret types.
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                if !f.alternate() {
+                    return std::fmt::Display::fmt(self, f);
+                }
+                let mut s = f.debug_struct(stringify!($name));
+                s.field("bits", &$name::BITS);
+                if $name::SECRET {
+                    s.field("value", &format_args!("<redacted>"));
+                } else {
+                    let uint: BigUint = (*self).into();
+                    s.field("hex", &format_args!("{:#x}", uint));
+                    s.field("decimal", &format_args!("{}", uint));
+                }
+                s.finish()
+            }
+        }
+
+        impl $name {
+            /// Whether the type was defined with the `little_endian` option.
+            pub const LITTLE_ENDIAN: bool =
+                $crate::__abstract_integer_options!(@has little_endian; $($opt)*);
+            /// Whether the type was defined with the `secret` option.
+            pub const SECRET: bool = $crate::__abstract_integer_options!(@has secret; $($opt)*);
+
+            fn max() -> BigUint {
+                BigUint::from(1u32).shl($bits) - 1u32
+            }
+
+            /// In debug builds, checks that the representation holds a value that fits in the
+            /// type. Every operation goes through this check, so that values corrupted by unsafe
+            /// code are detected early.
+            #[inline]
+            fn check_invariant(&self) {
+                let excess = self.0.len() * 8 - $bits;
+                let top = if $name::LITTLE_ENDIAN {
+                    self.0[self.0.len() - 1]
+                } else {
+                    self.0[0]
+                };
+                debug_assert!(
+                    excess == 0 || top >> (8 - excess) == 0,
+                    "invariant violated: value out of the bounds of type {}",
+                    stringify!($name)
+                );
+            }
+
+            fn hex_string_to_bytes(s: &str) -> Vec<u8> {
+                assert!(s.len() % 2 == 0, "length of hex string {}: {}",s, s.len());
+                let b: Result<Vec<u8>, ParseIntError> = (0..s.len())
+                    .step_by(2)
+                    .map(|i| u8::from_str_radix(&s[i..i + 2], 16))
+                    .collect();
+                b.expect("Error parsing hex string")
+            }
+
+            #[allow(dead_code)]
+            pub fn from_hex(s: &str) -> Self {
+                BigUint::from_bytes_be(&Self::hex_string_to_bytes(s)).into()
+            }
+
+            #[allow(dead_code)]
+            pub fn from_bytes_le(v: &[u8]) -> Self {
+                BigUint::from_bytes_le(v).into()
+            }
+
+            #[allow(dead_code)]
+            pub fn to_bytes_le(self) -> Vec<u8> {
+                BigUint::to_bytes_le(&self.into())
+            }
+
+            /// Returns the internal fixed-size representation: the value in the canonical byte
+            /// order of the type (big-endian unless defined with `little_endian`), padded with
+            /// zeros to the byte width of the type.
+            #[allow(dead_code)]
+            pub fn as_bytes(&self) -> &[u8; $name::BYTES] {
+                &self.0
+            }
+
+            /// Consumes the value and returns its fixed-size canonical representation, see
+            /// `as_bytes`.
+            #[allow(dead_code)]
+            pub fn into_bytes(self) -> [u8; $name::BYTES] {
+                self.0
+            }
+
+            /// Reads a value encoded in the canonical byte order of the type, see `as_bytes`.
+            /// The input can be shorter than the byte width of the type.
+            #[allow(dead_code)]
+            pub fn from_bytes(v: &[u8]) -> Self {
+                if $name::LITTLE_ENDIAN {
+                    BigUint::from_bytes_le(v).into()
+                } else {
+                    BigUint::from_bytes_be(v).into()
+                }
+            }
+
+            /// Reads a value like `from_bytes`, but returns `None` instead of panicking if it does
+            /// not fit in the type.
+            #[allow(dead_code)]
+            pub fn try_from_bytes(v: &[u8]) -> Option<Self> {
+                let x = if $name::LITTLE_ENDIAN {
+                    BigUint::from_bytes_le(v)
+                } else {
+                    BigUint::from_bytes_be(v)
+                };
+                if x > $name::max() {
+                    None
+                } else {
+                    Some(x.into())
+                }
+            }
+
+            /// Returns the big-endian encoding of the value without leading zeros, as used by
+            /// DER or MPI. Zero is encoded as the empty vector.
+            #[allow(dead_code)]
+            pub fn to_bytes_minimal(self) -> Vec<u8> {
+                let x: BigUint = self.into();
+                if x == BigUint::from(0u32) {
+                    Vec::new()
+                } else {
+                    x.to_bytes_be()
+                }
+            }
+
+            /// Reads a value from its minimal big-endian encoding, see `to_bytes_minimal`.
+            ///
+            /// **Warning**: panics if the encoding has leading zeros or if the value does not
+            /// fit in the type.
+            #[allow(dead_code)]
+            pub fn from_bytes_minimal(v: &[u8]) -> Self {
+                assert!(v.first() != Some(&0), "non-minimal encoding for type {}", stringify!($name));
+                BigUint::from_bytes_be(v).into()
+            }
+
+            /// Number of bits of the type, values are below `2^BITS`.
+            pub const BITS: usize = $bits;
+
+            /// Number of bytes of the canonical encoding, `BITS` rounded up to a byte.
+            pub const BYTES: usize = ($bits as usize).div_ceil(8);
+
+            /// Number of 64-bit limbs needed to hold a value of this type.
+            pub const LIMBS: usize = ($bits as usize).div_ceil(64);
+
+            /// Returns the value as 64-bit limbs, least significant limb first. Each limb is a
+            /// native `u64`, so the layout matches the usual little-endian limb arrays of
+            /// hand-written arithmetic.
+            #[allow(dead_code)]
+            pub fn as_limbs(&self) -> [u64; $name::LIMBS] {
+                let mut le_bytes = self.0;
+                if !$name::LITTLE_ENDIAN {
+                    le_bytes.reverse();
+                }
+                let mut limbs = [0u64; $name::LIMBS];
+                for (i, byte) in le_bytes.iter().enumerate() {
+                    limbs[i / 8] |= (*byte as u64) << (8 * (i % 8));
+                }
+                limbs
+            }
+
+            /// Builds a value from 64-bit limbs, least significant limb first.
+            ///
+            /// **Warning**: panics if the limbs encode a value too big for the type.
+            #[allow(dead_code)]
+            pub fn from_limbs(limbs: [u64; $name::LIMBS]) -> Self {
+                let bytes: Vec<u8> = limbs.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+                Self::from_bytes_le(&bytes)
+            }
+
+            /// Builds a value from 64-bit limbs, discarding the bits above `BITS`, with the same
+            /// operations for all the values.
+            #[doc(hidden)]
+            pub fn __from_limbs_masked(limbs: [u64; $name::LIMBS]) -> Self {
+                let mut bytes = [0u8; $name::BYTES];
+                for (i, byte) in bytes.iter_mut().enumerate() {
+                    *byte = (limbs[i / 8] >> (8 * (i % 8))) as u8;
+                }
+                bytes[$name::BYTES - 1] &= 0xffu8 >> (8 * $name::BYTES - $bits);
+                if !$name::LITTLE_ENDIAN {
+                    bytes.reverse();
+                }
+                $name(bytes)
+            }
+
+            /// Returns the non-adjacent form of the value, see `recoding`.
+            #[allow(dead_code)]
+            pub fn to_naf(self) -> Vec<i8> {
+                $crate::recoding::wnaf(&self.into(), 2)
+            }
+
+            /// Returns the width-`w` non-adjacent form of the value, see `recoding`.
+            ///
+            /// **Warning**: panics if `w` is not between 2 and 8.
+            #[allow(dead_code)]
+            pub fn to_wnaf(self, w: usize) -> Vec<i8> {
+                $crate::recoding::wnaf(&self.into(), w)
+            }
+
+            /// Returns the signed fixed-window recoding of width `w` of the value, with a number
+            /// of digits depending only on the type, see `recoding::signed_digits_ct`.
+            ///
+            /// **Warning**: panics if `w` is not between 2 and 8.
+            #[allow(dead_code)]
+            pub fn to_signed_digits_ct(self, w: usize) -> Vec<i8> {
+                $crate::recoding::signed_digits_ct(&self.as_limbs(), $name::BITS, w)
+            }
+
+            /// Returns the digits of the value in base `radix`, least significant first, without
+            /// leading zeros. Zero has no digits.
+            ///
+            /// **Warning**: panics if `radix` is below 2.
+            #[allow(dead_code)]
+            pub fn to_radix_digits(self, radix: u32) -> Vec<u32> {
+                assert!(radix >= 2, "invalid radix {}", radix);
+                let mut x: BigUint = self.into();
+                let mut digits = Vec::new();
+                while x != BigUint::from(0u32) {
+                    digits.push((&x % radix).to_u32_digits().first().copied().unwrap_or(0));
+                    x /= radix;
+                }
+                digits
+            }
+
+            /// Builds a value from its digits in base `radix`, least significant first.
+            ///
+            /// **Warning**: panics if `radix` is below 2, if a digit is not below `radix` or if
+            /// the value does not fit in the type.
+            #[allow(dead_code)]
+            pub fn from_radix_digits(digits: &[u32], radix: u32) -> Self {
+                assert!(radix >= 2, "invalid radix {}", radix);
+                digits
+                    .iter()
+                    .rev()
+                    .fold(BigUint::from(0u32), |acc, d| {
+                        assert!(*d < radix, "digit {} is not below the radix {}", d, radix);
+                        acc * radix + *d
+                    })
+                    .into()
+            }
+
+            /// Number of 32-bit words needed to hold a value of this type.
+            pub const WORDS32: usize = ($bits as usize).div_ceil(32);
+
+            /// Returns the value as 32-bit words, least significant word first.
+            #[allow(dead_code)]
+            pub fn to_u32s_le(self) -> [u32; $name::WORDS32] {
+                let limbs = self.as_limbs();
+                let mut words = [0u32; $name::WORDS32];
+                for (i, word) in words.iter_mut().enumerate() {
+                    *word = (limbs[i / 2] >> (32 * (i % 2))) as u32;
+                }
+                words
+            }
+
+            /// Returns the value as 32-bit words, most significant word first.
+            #[allow(dead_code)]
+            pub fn to_u32s_be(self) -> [u32; $name::WORDS32] {
+                let mut words = self.to_u32s_le();
+                words.reverse();
+                words
+            }
+
+            /// Returns the value as 64-bit words, least significant word first, same as
+            /// `as_limbs`.
+            #[allow(dead_code)]
+            pub fn to_u64s_le(self) -> [u64; $name::LIMBS] {
+                self.as_limbs()
+            }
+
+            /// Returns the value as 64-bit words, most significant word first.
+            #[allow(dead_code)]
+            pub fn to_u64s_be(self) -> [u64; $name::LIMBS] {
+                let mut words = self.as_limbs();
+                words.reverse();
+                words
+            }
+
+            /// Builds a value from 32-bit words, least significant word first.
+            ///
+            /// **Warning**: panics if the words encode a value too big for the type.
+            #[allow(dead_code)]
+            pub fn from_u32s_le(words: [u32; $name::WORDS32]) -> Self {
+                let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+                Self::from_bytes_le(&bytes)
+            }
+
+            /// Builds a value from 32-bit words, most significant word first.
+            ///
+            /// **Warning**: panics if the words encode a value too big for the type.
+            #[allow(dead_code)]
+            pub fn from_u32s_be(words: [u32; $name::WORDS32]) -> Self {
+                let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_be_bytes()).collect();
+                BigUint::from_bytes_be(&bytes).into()
+            }
+
+            /// Builds a value from 64-bit words, least significant word first, same as
+            /// `from_limbs`.
+            ///
+            /// **Warning**: panics if the words encode a value too big for the type.
+            #[allow(dead_code)]
+            pub fn from_u64s_le(words: [u64; $name::LIMBS]) -> Self {
+                Self::from_limbs(words)
+            }
+
+            /// Builds a value from 64-bit words, most significant word first.
+            ///
+            /// **Warning**: panics if the words encode a value too big for the type.
+            #[allow(dead_code)]
+            pub fn from_u64s_be(words: [u64; $name::LIMBS]) -> Self {
+                let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_be_bytes()).collect();
+                BigUint::from_bytes_be(&bytes).into()
+            }
+
+            #[allow(dead_code)]
+            pub fn from_literal(x: u128) -> Self {
+                let big_x = BigUint::from(x);
+                if big_x > $name::max().into() {
+                    let x = $crate::__redacted(x, $name::SECRET);
+                    panic!("literal {} too big for type {}", x, stringify!($name));
+                }
+                big_x.into()
+            }
+
+            #[doc(hidden)]
+            pub fn __from_nat_literal(s: &str) -> Self {
+                $crate::__parse_literal(s).into()
+            }
+
+            /// Exclusive upper bound of the values known at compile time beyond the bit width,
+            /// checked by `nat!`. Only the moduli of refined types give one.
+            #[doc(hidden)]
+            pub const __LITERAL_BOUND: Option<u128> = None;
+
+            /// Builds a value at compile time, for the constants blocks.
+            #[doc(hidden)]
+            pub const fn __from_u128_const(x: u128) -> Self {
+                assert!(
+                    (128 - x.leading_zeros()) as usize <= $name::BITS,
+                    concat!("constant too big for type ", stringify!($name))
+                );
+                let mut out = [0u8; $name::BYTES];
+                let n = if $name::BYTES < 16 { $name::BYTES } else { 16 };
+                let mut i = 0;
+                while i < n {
+                    let byte = (x >> (8 * i)) as u8;
+                    if $name::LITTLE_ENDIAN {
+                        out[i] = byte;
+                    } else {
+                        out[$name::BYTES - 1 - i] = byte;
+                    }
+                    i += 1;
+                }
+                $name(out)
+            }
+
+            /// Builds a value from a `u64` without going through `BigUint`, for the small
+            /// moduli of `tables`. The value must fit in the type.
+            #[doc(hidden)]
+            pub fn __from_u64(x: u64) -> Self {
+                let n = $name::BYTES.min(8);
+                debug_assert!(
+                    n == 8 || x >> (8 * n) == 0,
+                    "{} too big for type {}",
+                    $crate::__redacted(x, $name::SECRET),
+                    stringify!($name)
+                );
+                let mut out = [0u8; $name::BYTES];
+                out[..n].copy_from_slice(&x.to_le_bytes()[..n]);
+                if !$name::LITTLE_ENDIAN {
+                    out.reverse();
+                }
+                $name(out)
+            }
+
+            /// Returns the low 64 bits of the value without going through `BigUint`, see
+            /// `__from_u64`.
+            #[doc(hidden)]
+            pub fn __low_u64(&self) -> u64 {
+                let mut le_bytes = self.0;
+                if !$name::LITTLE_ENDIAN {
+                    le_bytes.reverse();
+                }
+                let n = $name::BYTES.min(8);
+                let mut low = [0u8; 8];
+                low[..n].copy_from_slice(&le_bytes[..n]);
+                u64::from_le_bytes(low)
+            }
+
+            #[allow(dead_code)]
+            pub fn from_signed_literal(x: i128) -> Self {
+                let big_x = BigUint::from(x as u128);
+                if big_x > $name::max().into() {
+                    let x = $crate::__redacted(x, $name::SECRET);
+                    panic!("literal {} too big for type {}", x, stringify!($name));
+                }
+                big_x.into()
+            }
+
+            /// Returns `self + 1`.
+            ///
+            /// **Warning**: panics on overflow.
+            #[allow(dead_code)]
+            pub fn inc(self) -> Self {
+                self + $name::from_literal(1)
+            }
+
+            /// Returns `self - 1`.
+            ///
+            /// **Warning**: panics on underflow.
+            #[allow(dead_code)]
+            pub fn dec(self) -> Self {
+                self - $name::from_literal(1)
+            }
+
+            /// Returns `self + 1`, or 0 if `self` is the largest value of the type.
+            #[allow(dead_code)]
+            pub fn wrapping_inc(self) -> Self {
+                if BigUint::from(self) == $name::max() {
+                    $name::default()
+                } else {
+                    self.inc()
+                }
+            }
+
+            /// Returns the nearest `f64`, or infinity if the value exceeds the range of `f64`.
+            #[allow(dead_code)]
+            pub fn to_f64_lossy(self) -> f64 {
+                $crate::__biguint_to_f64(&BigUint::from(self))
+            }
+
+            /// Converts a float holding an integer that fits in the type.
+            #[allow(dead_code)]
+            pub fn try_from_f64(x: f64) -> Result<Self, $crate::diagnostics::FromFloatError> {
+                let bound = $name::max() + 1u32;
+                $crate::__f64_to_biguint(x, stringify!($name), $name::SECRET, bound)
+                    .map(|x| x.into())
+            }
+
+            /// Wraps the value to compute with overflows promoted to unbounded values instead of
+            /// panicking, see `promotion`.
+            #[allow(dead_code)]
+            pub fn promoting(self) -> $crate::promotion::Promoting<$name> {
+                $crate::promotion::Promoting::Bounded(self)
+            }
+
+            /// Returns `self * self`, converting the operand only once.
+            ///
+            /// **Warning**: panics on overflow.
+            #[allow(dead_code)]
+            pub fn square(self) -> Self {
+                let a: BigUint = self.into();
+                let c = &a * &a;
+                if c > $name::max() {
+                    return $crate::diagnostics::ArithmeticPanic::new(
+                        stringify!($name),
+                        "*",
+                        $crate::diagnostics::ArithmeticErrorKind::Overflow,
+                        vec![a.clone(), a],
+                    )
+                    .secret($name::SECRET)
+                    .recover::<$name>()
+                    .into();
+                }
+                let r: $name = c.into();
+                $crate::shadow::check_checked(stringify!($name), "*", self, self, r);
+                r
+            }
+        }
+
+        /// **Warning**: panics on overflow.
+        impl Add for $name {
+            type Output = $name;
+            fn add(self, rhs: $name) -> $name {
+                let a: BigUint = self.into();
+                let b: BigUint = rhs.into();
+                let c = &a + &b;
+                if c > $name::max() {
+                    return $crate::diagnostics::ArithmeticPanic::new(
+                        stringify!($name),
+                        "+",
+                        $crate::diagnostics::ArithmeticErrorKind::Overflow,
+                        vec![a, b],
+                    )
+                    .secret($name::SECRET)
+                    .recover::<$name>()
+                    .into();
+                }
+                let r: $name = c.into();
+                $crate::shadow::check_checked(stringify!($name), "+", self, rhs, r);
+                r
+            }
+        }
+
+        /// **Warning**: panics on underflow.
+        impl Sub for $name {
+            type Output = $name;
+            fn sub(self, rhs: $name) -> $name {
+                let a: BigUint = self.into();
+                let b: BigUint = rhs.into();
+                let c = a.checked_sub(&b).unwrap_or_else(|| {
+                    $crate::diagnostics::ArithmeticPanic::new(
+                        stringify!($name),
+                        "-",
+                        $crate::diagnostics::ArithmeticErrorKind::Underflow,
+                        vec![a.clone(), b.clone()],
+                    )
+                    .secret($name::SECRET)
+                    .recover::<$name>()
+                });
+                let r: $name = c.into();
+                $crate::shadow::check_checked(stringify!($name), "-", self, rhs, r);
+                r
+            }
+        }
+
+        /// **Warning**: panics on overflow.
+        impl Mul for $name {
+            type Output = $name;
+            fn mul(self, rhs: $name) -> $name {
+                let a: BigUint = self.into();
+                let b: BigUint = rhs.into();
+                let c = &a * &b;
+                if c > $name::max() {
+                    return $crate::diagnostics::ArithmeticPanic::new(
+                        stringify!($name),
+                        "*",
+                        $crate::diagnostics::ArithmeticErrorKind::Overflow,
+                        vec![a, b],
+                    )
+                    .secret($name::SECRET)
+                    .recover::<$name>()
+                    .into();
+                }
+                let r: $name = c.into();
+                $crate::shadow::check_checked(stringify!($name), "*", self, rhs, r);
+                r
+            }
+        }
+
+        impl $name {
+            #[allow(dead_code)]
+            fn div_vartime(self, rhs: $name) -> $name {
+                let a: BigUint = self.into();
+                let b: BigUint = rhs.into();
+                if b == BigUint::zero() {
+                    return $crate::diagnostics::ArithmeticPanic::new(
+                        stringify!($name),
+                        "/",
+                        $crate::diagnostics::ArithmeticErrorKind::DivisionByZero,
+                        vec![a, b],
+                    )
+                    .secret($name::SECRET)
+                    .recover::<$name>()
+                    .into();
+                }
+                let c = a / b;
+                let r: $name = c.into();
+                $crate::shadow::check_checked(stringify!($name), "/", self, rhs, r);
+                r
+            }
+
+            #[allow(dead_code)]
+            fn rem_vartime(self, rhs: $name) -> $name {
+                let a: BigUint = self.into();
+                let b: BigUint = rhs.into();
+                if b == BigUint::zero() {
+                    return $crate::diagnostics::ArithmeticPanic::new(
+                        stringify!($name),
+                        "%",
+                        $crate::diagnostics::ArithmeticErrorKind::DivisionByZero,
+                        vec![a, b],
+                    )
+                    .secret($name::SECRET)
+                    .recover::<$name>()
+                    .into();
+                }
+                let c = a % b;
+                let r: $name = c.into();
+                $crate::shadow::check_checked(stringify!($name), "%", self, rhs, r);
+                r
+            }
+
+            fn inv_vartime(self, modval: Self) -> Self {
+                let biguintmodval : BigUint = modval.into();
+                let m = &biguintmodval - BigUint::from(2u32);
+                let s: BigUint = (self).into();
+                s.modpow(&m, &biguintmodval).into()
+            }
+
+            fn pow_felem_vartime(self, exp: Self, modval: Self) -> Self {
+                let a: BigUint = self.into();
+                let b: BigUint = exp.into();
+                let m: BigUint = modval.into();
+                let c: BigUint = a.modpow(&b, &m);
+                c.into()
+            }
+        }
+
+        $crate::__abstract_integer_options!(@unless secret; $($opt)*; {
+            $crate::__abstract_integer_options!(@unless no_div; $($opt)*; {
+                /// **Warning**: panics on division by 0.
+                impl Div for $name {
+                    type Output = $name;
+                    fn div(self, rhs: $name) -> $name {
+                        self.div_vartime(rhs)
+                    }
+                }
+
+                /// **Warning**: panics on division by 0.
+                impl Rem for $name {
+                    type Output = $name;
+                    fn rem(self, rhs: $name) -> $name {
+                        self.rem_vartime(rhs)
+                    }
+                }
+            });
+
+            impl $name {
+                #[allow(dead_code)]
+                pub fn inv(self, modval: Self) -> Self {
+                    self.inv_vartime(modval)
+                }
+
+                #[allow(dead_code)]
+                pub fn pow_felem(self, exp: Self, modval: Self) -> Self {
+                    self.pow_felem_vartime(exp, modval)
+                }
+                /// Returns self to the power of the argument.
+                /// The exponent is a u128.
+                #[allow(dead_code)]
+                pub fn pow(self, exp: u128, modval: Self) -> Self {
+                    self.pow_felem_vartime(BigUint::from(exp).into(), modval)
+                }
+
+                /// Precomputes the reciprocal of `self` to divide by it repeatedly, see
+                /// `division`.
+                ///
+                /// **Warning**: panics if `self` is 0.
+                #[allow(dead_code)]
+                pub fn divisor_context(self) -> $crate::division::DivisorContext<$name> {
+                    $crate::division::DivisorContext::new(self)
+                }
+            }
+        });
+
+        $crate::__abstract_integer_options!(@if secret; $($opt)*; {
+            impl $name {
+                /// Returns the explicitly variable-time view of the value, see `vartime`.
+                #[allow(dead_code)]
+                pub fn var_time(self) -> $crate::vartime::VarTime<$name> {
+                    $crate::vartime::VarTime(self)
+                }
+            }
+
+            /// **Warning**: panics on division by 0.
+            impl Div<$name> for $crate::vartime::VarTime<$name> {
+                type Output = $name;
+                fn div(self, rhs: $name) -> $name {
+                    self.0.div_vartime(rhs)
+                }
+            }
+
+            /// **Warning**: panics on division by 0.
+            impl Rem<$name> for $crate::vartime::VarTime<$name> {
+                type Output = $name;
+                fn rem(self, rhs: $name) -> $name {
+                    self.0.rem_vartime(rhs)
+                }
+            }
+
+            impl $crate::vartime::VarTimeOps<$name> for $crate::vartime::VarTime<$name> {
+                fn inv(self, modval: $name) -> $name {
+                    self.0.inv_vartime(modval)
+                }
+
+                fn pow_felem(self, exp: $name, modval: $name) -> $name {
+                    self.0.pow_felem_vartime(exp, modval)
+                }
+
+                fn pow(self, exp: u128, modval: $name) -> $name {
+                    self.0.pow_felem_vartime(BigUint::from(exp).into(), modval)
+                }
+            }
+        });
+
+        /// **Warning**: panics if non-zero bits are shifted out of the type.
+        impl Shl<usize> for $name {
+            type Output = $name;
+            fn shl(self, rhs: usize) -> $name {
+                self.checked_shl(rhs).unwrap_or_else(|| {
+                    $crate::diagnostics::ArithmeticPanic::new(
+                        stringify!($name),
+                        "<<",
+                        $crate::diagnostics::ArithmeticErrorKind::LostBits,
+                        vec![self.into(), BigUint::from(rhs)],
+                    )
+                    .secret($name::SECRET)
+                    .recover::<$name>()
+                    .into()
+                })
+            }
+        }
+
+        /// **Warning**: panics if non-zero bits are shifted out of the type.
+        impl Shr<usize> for $name {
+            type Output = $name;
+            fn shr(self, rhs: usize) -> $name {
+                self.checked_shr(rhs).unwrap_or_else(|| {
+                    $crate::diagnostics::ArithmeticPanic::new(
+                        stringify!($name),
+                        ">>",
+                        $crate::diagnostics::ArithmeticErrorKind::LostBits,
+                        vec![self.into(), BigUint::from(rhs)],
+                    )
+                    .secret($name::SECRET)
+                    .recover::<$name>()
+                    .into()
+                })
+            }
+        }
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &$name) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        /// Compares the canonical representations directly, without allocating.
+        impl Ord for $name {
+            fn cmp(&self, other: &$name) -> std::cmp::Ordering {
+                if $name::LITTLE_ENDIAN {
+                    self.0.iter().rev().cmp(other.0.iter().rev())
+                } else {
+                    self.0.cmp(&other.0)
+                }
+            }
+        }
+
+        impl $name {
+            /// Returns 2 to the power of the argument
+            #[allow(dead_code)]
+            pub fn pow2(x: usize) -> $name {
+                BigUint::from(1u32).shl(x).into()
+            }
+
+            /// Gets the `i`-th least significant bit of this integer.
+            #[allow(dead_code)]
+            pub fn bit(self, i: usize) -> bool {
+                assert!(
+                    i < self.0.len() * 8,
+                    "the bit queried should be lower than the size of the integer representation: {} < {}",
+                    i,
+                    self.0.len() * 8
+                );
+                let bigint : BigUint = self.into();
+                let tmp: BigUint = bigint >> i;
+                (tmp & BigUint::from(1u128)).to_bytes_le()[0] == 1
+            }
+
+            /// Gets the least significant bit of this integer.
+            #[allow(dead_code)]
+            pub fn lsb(self) -> bool {
+                let low = if $name::LITTLE_ENDIAN {
+                    self.0[0]
+                } else {
+                    self.0[self.0.len() - 1]
+                };
+                low & 1 == 1
+            }
+
+            #[allow(dead_code)]
+            pub fn is_odd(self) -> bool {
+                self.lsb()
+            }
+
+            #[allow(dead_code)]
+            pub fn is_even(self) -> bool {
+                !self.lsb()
+            }
+
+            /// Returns the number of trailing zero bits, which is the number of bits of the type
+            /// for zero.
+            #[allow(dead_code)]
+            pub fn trailing_zeros(self) -> usize {
+                let mut le_bytes = self.0;
+                if !$name::LITTLE_ENDIAN {
+                    le_bytes.reverse();
+                }
+                match le_bytes.iter().position(|byte| *byte != 0) {
+                    Some(i) => 8 * i + le_bytes[i].trailing_zeros() as usize,
+                    None => $bits,
+                }
+            }
+
+            /// Returns `(s, q)` such that `self - 1 = 2^s * q` with `q` odd, as needed by
+            /// Miller-Rabin or Tonelli-Shanks.
+            ///
+            /// **Warning**: panics if self is 0 or 1.
+            #[allow(dead_code)]
+            pub fn decompose_2adic(self) -> (usize, Self) {
+                let a: BigUint = self.into();
+                assert!(
+                    a > BigUint::from(1u32),
+                    "cannot decompose {} - 1 for type {}",
+                    $crate::__redacted(&a, $name::SECRET),
+                    stringify!($name)
+                );
+                let n_minus_one: Self = (a - 1u32).into();
+                let s = n_minus_one.trailing_zeros();
+                let q: BigUint = BigUint::from(n_minus_one) >> s;
+                (s, q.into())
+            }
+
+            /// Returns the integer `n`-th root of self, rounded down.
+            ///
+            /// **Warning**: panics if `n` is 0.
+            #[allow(dead_code)]
+            pub fn nth_root(self, n: u32) -> Self {
+                assert!(n > 0, "0-th root in type {}", stringify!($name));
+                let a: BigUint = self.into();
+                a.nth_root(n).into()
+            }
+
+            /// Returns `true` if self is `m^k` for some integers `m >= 2` and `k >= 2`.
+            #[allow(dead_code)]
+            pub fn is_perfect_power(self) -> bool {
+                $crate::number_theory::perfect_power(&self.into()).is_some()
+            }
+
+            /// Deterministic Miller-Rabin primality test with the first `rounds` primes as
+            /// bases, see `number_theory::is_probable_prime_fixed_bases`.
+            ///
+            /// **Warning**: panics if `rounds` is above 25.
+            #[allow(dead_code)]
+            pub fn is_probable_prime_fixed_bases(self, rounds: usize) -> bool {
+                $crate::number_theory::is_probable_prime_fixed_bases(&self.into(), rounds)
+            }
+
+            /// Deterministic Baillie-PSW primality test, see `number_theory::is_prime_bpsw`.
+            #[allow(dead_code)]
+            pub fn is_prime_bpsw(self) -> bool {
+                $crate::number_theory::is_prime_bpsw(&self.into())
+            }
+
+            /// Returns the prime factorization of self as `(prime, exponent)` pairs in increasing
+            /// order, see `number_theory::factor`.
+            ///
+            /// **Warning**: best effort, only meant for tests and tooling. Panics if self is 0.
+            #[allow(dead_code)]
+            pub fn factor(self) -> Vec<(Self, u32)> {
+                $crate::number_theory::factor(&self.into())
+                    .into_iter()
+                    .map(|(p, e)| (p.into(), e))
+                    .collect()
+            }
+
+            /// Returns the Jacobi symbol `(self / n)`, see `number_theory::jacobi`.
+            ///
+            /// **Warning**: panics if `n` is even.
+            #[allow(dead_code)]
+            pub fn jacobi(self, n: Self) -> i8 {
+                $crate::number_theory::jacobi(&self.into(), &n.into())
+            }
+
+            /// Shifts left, returning `None` if non-zero bits would be shifted out of the type.
+            #[allow(dead_code)]
+            pub fn checked_shl(self, shift: usize) -> Option<Self> {
+                let a: BigUint = self.into();
+                if a.bits() == 0 {
+                    return Some(self);
+                }
+                // Checked before shifting so that huge shifts do not allocate.
+                if shift >= $name::BITS || a.bits() > $name::BITS - shift {
+                    return None;
+                }
+                Some((a << shift).into())
+            }
+
+            /// Shifts right, returning `None` if non-zero bits would be shifted out of the type.
+            #[allow(dead_code)]
+            pub fn checked_shr(self, shift: usize) -> Option<Self> {
+                let a: BigUint = self.into();
+                // Checked before shifting back so that huge shifts do not allocate.
+                if shift >= $name::BITS {
+                    return if a.bits() == 0 { Some(self) } else { None };
+                }
+                let c: BigUint = &a >> shift;
+                if &c << shift != a {
+                    None
+                } else {
+                    Some(c.into())
+                }
+            }
+
+            /// Swaps `a` and `b` if `choice` is true, without branching on `choice`.
+            #[allow(dead_code)]
+            pub fn conditional_swap(a: &mut Self, b: &mut Self, choice: bool) {
+                let mask = (choice as u8).wrapping_neg();
+                for (x, y) in a.0.iter_mut().zip(b.0.iter_mut()) {
+                    let t = mask & (*x ^ *y);
+                    *x ^= t;
+                    *y ^= t;
+                }
+            }
+
+            /// Returns self to the power of the argument modulo `modval`, using a Montgomery
+            /// ladder. The same sequence of multiplications, squarings and conditional swaps is
+            /// performed for all 128 bits of the exponent, whatever their values. Note that the
+            /// underlying `BigUint` arithmetic is not itself constant-time.
+            #[allow(dead_code)]
+            pub fn pow_ct(self, exp: u128, modval: Self) -> Self {
+                let m: BigUint = modval.into();
+                let s: BigUint = self.into();
+                let mut r0: Self = (BigUint::from(1u32) % &m).into();
+                let mut r1: Self = (s % &m).into();
+                for i in (0..128).rev() {
+                    let bit = (exp >> i) & 1 == 1;
+                    Self::conditional_swap(&mut r0, &mut r1, bit);
+                    let a: BigUint = r0.into();
+                    let b: BigUint = r1.into();
+                    r1 = (&a * &b % &m).into();
+                    r0 = (&a * &a % &m).into();
+                    Self::conditional_swap(&mut r0, &mut r1, bit);
+                }
+                r0
+            }
+        }
+    };
+}
+
+/// Defines several bounded natural integers at once, each with the same semantics as
+/// `define_abstract_integer_checked`:
+///
+/// ```
+/// # use abstract_integers::*;
+/// # use num::{BigUint, CheckedSub, Zero};
+/// # use std::num::ParseIntError;
+/// # use std::ops::*;
+/// define_abstract_integers! { U8: 8, U16: 16, U32: 32, U64: 64, U128: 128 }
+/// ```
+#[macro_export]
+macro_rules! define_abstract_integers {
+    ($($name:ident : $bits:literal),* $(,)?) => {
+        $(
+            $crate::define_abstract_integer_checked!($name, $bits);
+        )*
+    };
+}
+
+/// Declares that the first checked type has twice the number of bits of the second one, which
+/// is checked at compile time:
+///
+/// ```
+/// # use abstract_integers::*;
+/// # use num::{BigUint, CheckedSub, Zero};
+/// # use std::num::ParseIntError;
+/// # use std::ops::*;
+/// define_abstract_integer_checked!(Nat, 256);
+/// define_abstract_integer_checked!(NatWide, 512);
+/// define_double_width!(NatWide, Nat);
+/// ```
+///
+/// This generates the lossless conversion `From<Nat> for NatWide`, the fallible conversion
+/// `TryFrom<NatWide> for Nat`, and the `NatWide::split`/`NatWide::combine` methods converting
+/// between a wide value and its high and low halves. The wide type implements `DoubleWidth`,
+/// which lets refined types over the narrow type reduce wide values with `reduce_wide`.
+#[macro_export]
+macro_rules! define_double_width {
+    ($wide:ident, $narrow:ident) => {
+        const _: () = assert!(
+            $wide::BITS == 2 * $narrow::BITS,
+            concat!(
+                stringify!($wide),
+                " does not have twice the number of bits of ",
+                stringify!($narrow)
+            )
+        );
+
+        impl $crate::traits::DoubleWidth for $wide {
+            type Narrow = $narrow;
+        }
+
+        impl From<$narrow> for $wide {
+            fn from(x: $narrow) -> $wide {
+                BigUint::from(x).into()
+            }
+        }
+
+        impl std::convert::TryFrom<$wide> for $narrow {
+            type Error = $crate::diagnostics::OutOfRangeError;
+
+            fn try_from(x: $wide) -> Result<$narrow, Self::Error> {
+                let value = BigUint::from(x);
+                if value.bits() > $narrow::BITS {
+                    Err($crate::diagnostics::OutOfRangeError::new(stringify!($narrow), value)
+                        .secret($narrow::SECRET))
+                } else {
+                    Ok(value.into())
+                }
+            }
+        }
+
+        impl $wide {
+            /// Splits the value into its high and low halves.
+            #[allow(dead_code)]
+            pub fn split(self) -> ($narrow, $narrow) {
+                let value = BigUint::from(self);
+                let low_mask = (BigUint::from(1u32) << $narrow::BITS) - 1u32;
+                let low = &value & low_mask;
+                ((value >> $narrow::BITS).into(), low.into())
+            }
+
+            /// Builds a value from its high and low halves.
+            #[allow(dead_code)]
+            pub fn combine(high: $narrow, low: $narrow) -> $wide {
+                let high = BigUint::from(high) << $narrow::BITS;
+                (high + BigUint::from(low)).into()
+            }
+        }
+    };
+}
+
+/// Declares that values of the first checked type can be cut into a high part of the second type
+/// and a low part of the third one:
+///
+/// ```
+/// # use abstract_integers::*;
+/// # use num::{BigUint, CheckedSub, Zero};
+/// # use std::num::ParseIntError;
+/// # use std::ops::*;
+/// # define_abstract_integer_checked!(Encoded, 256);
+/// # define_abstract_integer_checked!(SignBit, 1);
+/// # define_abstract_integer_checked!(FieldRepr, 255);
+/// define_bit_concat!(Encoded, SignBit, FieldRepr);
+/// # let encoded = Encoded::pow2(255) + Encoded::from_literal(9);
+/// let (sign, y) = encoded.split_at(255);
+/// let encoded = Encoded::concat(sign, y);
+/// # assert_eq!(sign, SignBit::from_literal(1));
+/// ```
+///
+/// `Whole::split_at(k)` returns the bits above position `k` and the `k` lowest bits, and panics
+/// if the parts do not always fit their types for this `k`. `Whole::concat(high, low)` is the
+/// inverse of `split_at(Low::BITS)` and panics if the result overflows. It is a compile-time
+/// error to declare a relation for which no split point is valid.
+#[macro_export]
+macro_rules! define_bit_concat {
+    ($whole:ident, $high:ident, $low:ident) => {
+        const _: () = assert!(
+            $high::BITS + $low::BITS >= $whole::BITS,
+            concat!(
+                stringify!($whole),
+                " is wider than ",
+                stringify!($high),
+                " and ",
+                stringify!($low),
+                " together"
+            )
+        );
+
+        impl $whole {
+            /// Splits the value at bit `k`, returning the bits above `k` and the `k` lowest bits.
+            #[allow(dead_code)]
+            pub fn split_at(self, k: usize) -> ($high, $low) {
+                assert!(
+                    k <= $low::BITS && $whole::BITS <= k + $high::BITS,
+                    "cannot split {} at bit {} into {} and {}",
+                    stringify!($whole),
+                    k,
+                    stringify!($high),
+                    stringify!($low)
+                );
+                let value = BigUint::from(self);
+                let low_mask = (BigUint::from(1u32) << k) - 1u32;
+                let low = &value & low_mask;
+                ((value >> k).into(), low.into())
+            }
+
+            /// Places `high` above the `Low::BITS` bits of `low`.
+            #[allow(dead_code)]
+            pub fn concat(high: $high, low: $low) -> $whole {
+                let high = BigUint::from(high) << $low::BITS;
+                (high + BigUint::from(low)).into()
+            }
+        }
+    };
+}
+
+/// Defines a fixed-point type over a checked type: the value of `Name(x)` is `x / 2^F` with `F`
+/// the number of fractional bits, checked at compile time to be below the bits of the base.
+///
+/// ```
+/// # use abstract_integers::*;
+/// # use num::{BigUint, CheckedSub, Zero};
+/// # use std::num::ParseIntError;
+/// # use std::ops::*;
+/// define_abstract_integer_checked!(Nat128, 128);
+/// define_fixed_point!(Rate, Nat128, 64);
+/// let r = Rate::from_integer(3) / Rate::from_integer(4);
+/// assert_eq!(format!("{}", r), "0.75");
+/// ```
+///
+/// Addition and subtraction are those of the base. Multiplication and division are rounded
+/// towards zero. All the operations panic on overflow, underflow and division by zero.
+#[macro_export]
+macro_rules! define_fixed_point {
+    ($name:ident, $base:ident, $frac:expr) => {
+        const _: () = assert!(
+            ($frac as usize) < $base::BITS,
+            concat!("too many fractional bits for base type ", stringify!($base))
+        );
+
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+        pub struct $name($base);
+
+        impl $name {
+            /// Number of fractional bits.
+            pub const FRACTIONAL_BITS: usize = $frac;
+
+            /// Returns the value whose representation is `x`, that is `x / 2^FRACTIONAL_BITS`.
+            #[allow(dead_code)]
+            pub fn from_raw(x: $base) -> Self {
+                $name(x)
+            }
+
+            /// Returns the representation of the value, scaled by `2^FRACTIONAL_BITS`.
+            #[allow(dead_code)]
+            pub fn raw(self) -> $base {
+                self.0
+            }
+
+            /// **Warning**: panics if the integer does not fit in the type.
+            #[allow(dead_code)]
+            pub fn from_integer(x: u128) -> Self {
+                $name::checked_from(
+                    "from_integer",
+                    vec![BigUint::from(x)],
+                    BigUint::from(x) << $name::FRACTIONAL_BITS,
+                )
+            }
+
+            /// Returns the integer part of the value.
+            #[allow(dead_code)]
+            pub fn floor(self) -> $base {
+                (BigUint::from(self.0) >> $name::FRACTIONAL_BITS).into()
+            }
+
+            fn checked_from(operation: &'static str, operands: Vec<BigUint>, x: BigUint) -> Self {
+                if x > <$base as $crate::traits::CheckedInteger>::max_value() {
+                    return $name($crate::diagnostics::ArithmeticPanic::new(
+                        stringify!($name),
+                        operation,
+                        $crate::diagnostics::ArithmeticErrorKind::Overflow,
+                        operands,
+                    )
+                    .secret($base::SECRET)
+                    .recover::<$name>()
+                    .into());
+                }
+                $name(x.into())
+            }
+        }
+
+        /// **Warning**: panics on overflow.
+        impl Add for $name {
+            type Output = $name;
+            fn add(self, rhs: $name) -> $name {
+                $name(self.0 + rhs.0)
+            }
+        }
+
+        /// **Warning**: panics on underflow.
+        impl Sub for $name {
+            type Output = $name;
+            fn sub(self, rhs: $name) -> $name {
+                $name(self.0 - rhs.0)
+            }
+        }
+
+        /// **Warning**: panics on overflow.
+        impl Mul for $name {
+            type Output = $name;
+            fn mul(self, rhs: $name) -> $name {
+                let a = BigUint::from(self.0);
+                let b = BigUint::from(rhs.0);
+                let c = (&a * &b) >> $name::FRACTIONAL_BITS;
+                $name::checked_from("*", vec![a, b], c)
+            }
+        }
+
+        /// **Warning**: panics on overflow and division by 0.
+        impl Div for $name {
+            type Output = $name;
+            fn div(self, rhs: $name) -> $name {
+                let a = BigUint::from(self.0);
+                let b = BigUint::from(rhs.0);
+                if b == BigUint::from(0u32) {
+                    return $name($crate::diagnostics::ArithmeticPanic::new(
+                        stringify!($name),
+                        "/",
+                        $crate::diagnostics::ArithmeticErrorKind::DivisionByZero,
+                        vec![a, b],
+                    )
+                    .secret($base::SECRET)
+                    .recover::<$name>()
+                    .into());
+                }
+                let c = (&a << $name::FRACTIONAL_BITS) / &b;
+                $name::checked_from("/", vec![a, b], c)
+            }
+        }
+
+        /// Prints the exact decimal expansion of the value, which is finite.
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                let x = BigUint::from(self.0);
+                let mask = (BigUint::from(1u32) << $name::FRACTIONAL_BITS) - 1u32;
+                let mut frac = &x & &mask;
+                write!(f, "{}", x >> $name::FRACTIONAL_BITS)?;
+                if frac != BigUint::from(0u32) {
+                    write!(f, ".")?;
+                }
+                while frac != BigUint::from(0u32) {
+                    frac *= 10u32;
+                    write!(f, "{}", &frac >> $name::FRACTIONAL_BITS)?;
+                    frac &= &mask;
+                }
+                Ok(())
+            }
+        }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{}", self)
+            }
+        }
+    };
+}
+
+/// Defines a machine word of the given number of bits, with the wrapping semantics of the
+/// primitive unsigned integers, for specifications of hash functions and ciphers:
+///
+/// ```
+/// # use abstract_integers::*;
+/// # use num::{BigUint, CheckedSub, Zero};
+/// # use std::num::ParseIntError;
+/// # use std::ops::*;
+/// define_machine_integer!(U32spec, 32);
+/// let x = U32spec::from_literal(0xffff_ffff) + U32spec::from_literal(2);
+/// assert_eq!(x, U32spec::from_literal(1));
+/// assert_eq!(x.rotate_right(1), U32spec::from_literal(0x8000_0000));
+/// ```
+///
+/// The value is stored in a checked type of the same width, named after the machine type with a
+/// `Repr` suffix. Addition, subtraction and multiplication wrap modulo `2^BITS`, and so do the
+/// conversions from `BigUint` and primitive integers. The type also supports shifts, rotations
+/// and bitwise operations. All the operations are computed on the fixed number of 64-bit limbs
+/// of the width, with the same operations whatever the values.
+///
+/// With the `secret` option, the word is classified like the secret integers of hacspec: it is
+/// redacted when printed and does not implement `PartialEq` nor `Ord`, so that no branch can
+/// depend on it. Comparisons are instead computed in constant time as masks, all ones when the
+/// comparison holds and zero otherwise, to be combined with `select` or the bitwise operations.
+/// The conversions to `BigUint` and to the representation type are not implemented, `declassify`
+/// explicitly makes the value public:
+///
+/// ```
+/// # use abstract_integers::*;
+/// # use num::{BigUint, CheckedSub, Zero};
+/// # use std::num::ParseIntError;
+/// # use std::ops::*;
+/// define_machine_integer!(SecretU8, 8, secret);
+/// # let s = SecretU8::from;
+/// # let (a, b, x, y) = (s(3u8), s(3u8), s(0u8), s(1u8));
+/// let m = a.eq_mask(b);
+/// let c = m.select(x, y);
+/// if c.declassify() == BigUint::from(0u32) {
+///     // ...
+/// }
+/// ```
+#[macro_export]
+macro_rules! define_machine_integer {
+    ($(#[$attr:meta])* $name:ident, $bits:expr $(, $opt:ident)* $(,)?) => {
+        $crate::__abstract_integer_machine_options!($($opt)*);
+
+        $crate::paste::paste! {
+            $crate::define_abstract_integer_checked!(
+                #[doc = concat!("Representation of `", stringify!($name), "`.")]
+                [<$name Repr>],
+                $bits
+                $(, $opt)*
+            );
+
+            $(#[$attr])*
+            #[derive(Clone, Copy, Default)]
+            #[repr(transparent)]
+            pub struct $name([<$name Repr>]);
+
+            $crate::__abstract_integer_options!(@unless secret; $($opt)*; {
+                impl PartialEq for $name {
+                    fn eq(&self, other: &$name) -> bool {
+                        self.0 == other.0
+                    }
+                }
+
+                impl Eq for $name {}
+
+                impl PartialOrd for $name {
+                    fn partial_cmp(&self, other: &$name) -> Option<std::cmp::Ordering> {
+                        Some(self.cmp(other))
+                    }
+                }
+
+                impl Ord for $name {
+                    fn cmp(&self, other: &$name) -> std::cmp::Ordering {
+                        self.0.cmp(&other.0)
+                    }
+                }
+            });
+
+            $crate::__abstract_integer_options!(@if secret; $($opt)*; {
+                impl $name {
+                    /// Returns the value, explicitly making it public.
+                    #[allow(dead_code)]
+                    pub fn declassify(self) -> BigUint {
+                        self.0.into()
+                    }
+
+                    /// Returns all ones if the bit is 1 and zero if it is 0, without branching.
+                    fn from_bit(bit: u64) -> $name {
+                        $name::from_limbs_wrapping([bit.wrapping_neg(); [<$name Repr>]::LIMBS])
+                    }
+
+                    /// Returns the borrow of `a - b`, 1 if `a < b` and 0 otherwise, computed on
+                    /// all the limbs without branching.
+                    fn borrow(a: $name, b: $name) -> u64 {
+                        let (a, b) = (a.limbs(), b.limbs());
+                        let mut borrow = 0u64;
+                        for (x, y) in a.iter().zip(b.iter()) {
+                            let d = (*x as u128).wrapping_sub(*y as u128 + borrow as u128);
+                            borrow = (d >> 127) as u64;
+                        }
+                        borrow
+                    }
+
+                    /// Returns all ones if the values are equal, zero otherwise, in constant
+                    /// time.
+                    #[allow(dead_code)]
+                    pub fn eq_mask(self, rhs: $name) -> $name {
+                        let (a, b) = (self.limbs(), rhs.limbs());
+                        let diff = a.iter().zip(b.iter()).fold(0u64, |acc, (x, y)| acc | (x ^ y));
+                        $name::from_bit(1 ^ ((diff | diff.wrapping_neg()) >> 63))
+                    }
+
+                    /// Returns all ones if the values differ, zero otherwise, in constant time.
+                    #[allow(dead_code)]
+                    pub fn ne_mask(self, rhs: $name) -> $name {
+                        !self.eq_mask(rhs)
+                    }
+
+                    /// Returns all ones if `self < rhs`, zero otherwise, in constant time.
+                    #[allow(dead_code)]
+                    pub fn lt_mask(self, rhs: $name) -> $name {
+                        $name::from_bit($name::borrow(self, rhs))
+                    }
+
+                    /// Returns all ones if `self <= rhs`, zero otherwise, in constant time.
+                    #[allow(dead_code)]
+                    pub fn le_mask(self, rhs: $name) -> $name {
+                        $name::from_bit(1 ^ $name::borrow(rhs, self))
+                    }
+
+                    /// Returns all ones if `self > rhs`, zero otherwise, in constant time.
+                    #[allow(dead_code)]
+                    pub fn gt_mask(self, rhs: $name) -> $name {
+                        rhs.lt_mask(self)
+                    }
+
+                    /// Returns all ones if `self >= rhs`, zero otherwise, in constant time.
+                    #[allow(dead_code)]
+                    pub fn ge_mask(self, rhs: $name) -> $name {
+                        rhs.le_mask(self)
+                    }
+
+                    /// Returns the bits of `a` where self is set and the bits of `b` elsewhere,
+                    /// that is `a` if self is a mask of all ones and `b` if it is zero.
+                    #[allow(dead_code)]
+                    pub fn select(self, a: $name, b: $name) -> $name {
+                        (a & self) | (b & !self)
+                    }
+                }
+            });
+
+            impl $name {
+                /// Number of bits of the word.
+                pub const BITS: usize = $bits;
+
+                /// Number of bytes of the canonical encoding, `BITS` rounded up to a byte.
+                #[allow(dead_code)]
+                pub const BYTES: usize = [<$name Repr>]::BYTES;
+
+                /// Whether the type was defined with the `secret` option.
+                pub const SECRET: bool = [<$name Repr>]::SECRET;
+
+                /// Reduces the value modulo `2^BITS`.
+                fn wrap(x: BigUint) -> $name {
+                    let mask = (BigUint::from(1u32) << $name::BITS) - 1u32;
+                    $name((x & mask).into())
+                }
+
+                /// The value as 64-bit limbs, least significant first.
+                fn limbs(self) -> [u64; [<$name Repr>]::LIMBS] {
+                    self.0.as_limbs()
+                }
+
+                /// Builds a word from 64-bit limbs, discarding the bits above `BITS`.
+                fn from_limbs_wrapping(limbs: [u64; [<$name Repr>]::LIMBS]) -> $name {
+                    $name([<$name Repr>]::__from_limbs_masked(limbs))
+                }
+
+                /// Applies `op` to the limbs of both words.
+                fn map_limbs(self, rhs: $name, op: impl Fn(u64, u64) -> u64) -> $name {
+                    let (mut a, b) = (self.limbs(), rhs.limbs());
+                    for (x, y) in a.iter_mut().zip(b.iter()) {
+                        *x = op(*x, *y);
+                    }
+                    $name::from_limbs_wrapping(a)
+                }
+
+                /// Shifts left by `n` places, `n` being below `BITS`. The operations only depend
+                /// on `n`.
+                fn shl_bits(self, n: usize) -> $name {
+                    let a = self.limbs();
+                    let mut out = [0u64; [<$name Repr>]::LIMBS];
+                    let (q, r) = (n / 64, n % 64);
+                    for i in q..out.len() {
+                        out[i] = a[i - q] << r;
+                        if r > 0 && i > q {
+                            out[i] |= a[i - q - 1] >> (64 - r);
+                        }
+                    }
+                    $name::from_limbs_wrapping(out)
+                }
+
+                /// Shifts right by `n` places, `n` being below `BITS`. The operations only depend
+                /// on `n`.
+                fn shr_bits(self, n: usize) -> $name {
+                    let a = self.limbs();
+                    let mut out = [0u64; [<$name Repr>]::LIMBS];
+                    let (q, r) = (n / 64, n % 64);
+                    for i in 0..out.len() - q {
+                        out[i] = a[i + q] >> r;
+                        if r > 0 && i + q + 1 < a.len() {
+                            out[i] |= a[i + q + 1] << (64 - r);
+                        }
+                    }
+                    $name::from_limbs_wrapping(out)
+                }
+
+                /// **Warning**: panics if the literal does not fit in the type.
+                #[allow(dead_code)]
+                pub fn from_literal(x: u128) -> Self {
+                    $name([<$name Repr>]::from_literal(x))
+                }
+
+                /// Rotates the bits left by `n` places, modulo the number of bits.
+                #[allow(dead_code)]
+                pub fn rotate_left(self, n: u32) -> Self {
+                    let n = n as usize % $name::BITS;
+                    if n == 0 {
+                        return self;
+                    }
+                    self.shl_bits(n) | self.shr_bits($name::BITS - n)
+                }
+
+                /// Rotates the bits right by `n` places, modulo the number of bits.
+                #[allow(dead_code)]
+                pub fn rotate_right(self, n: u32) -> Self {
+                    let n = n as usize % $name::BITS;
+                    self.rotate_left(($name::BITS - n) as u32)
+                }
+            }
+
+            impl $crate::traits::MachineInteger for $name {
+                const BITS: usize = $name::BITS;
+                const SECRET: bool = $name::SECRET;
+
+                fn declassify(self) -> BigUint {
+                    self.0.into()
+                }
+            }
+
+            impl From<[<$name Repr>]> for $name {
+                fn from(x: [<$name Repr>]) -> $name {
+                    $name(x)
+                }
+            }
+
+            $crate::__abstract_integer_options!(@unless secret; $($opt)*; {
+                impl From<$name> for [<$name Repr>] {
+                    fn from(x: $name) -> [<$name Repr>] {
+                        x.0
+                    }
+                }
+
+                impl From<$name> for BigUint {
+                    fn from(x: $name) -> BigUint {
+                        x.0.into()
+                    }
+                }
+
+                $crate::__abstract_integer_any!($name);
+            });
+
+            /// Reduces the value modulo `2^BITS`.
+            impl From<BigUint> for $name {
+                fn from(x: BigUint) -> $name {
+                    $name::wrap(x)
+                }
+            }
+
+            $crate::__abstract_integer_from_primitives!($name, u8 u16 u32 u64 usize);
+
+            impl std::fmt::Display for $name {
+                fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    write!(f, "{}", self.0)
+                }
+            }
+
+            impl std::fmt::Debug for $name {
+                fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    write!(f, "{}", self)
+                }
+            }
+
+            /// **Warning**: wraps on overflow.
+            impl Add for $name {
+                type Output = $name;
+                fn add(self, rhs: $name) -> $name {
+                    let (mut a, b) = (self.limbs(), rhs.limbs());
+                    let mut carry = 0u128;
+                    for (x, y) in a.iter_mut().zip(b.iter()) {
+                        let s = *x as u128 + *y as u128 + carry;
+                        *x = s as u64;
+                        carry = s >> 64;
+                    }
+                    $name::from_limbs_wrapping(a)
+                }
+            }
+
+            /// **Warning**: wraps on underflow.
+            impl Sub for $name {
+                type Output = $name;
+                fn sub(self, rhs: $name) -> $name {
+                    let (mut a, b) = (self.limbs(), rhs.limbs());
+                    let mut borrow = 0u128;
+                    for (x, y) in a.iter_mut().zip(b.iter()) {
+                        let d = (*x as u128).wrapping_sub(*y as u128 + borrow);
+                        *x = d as u64;
+                        borrow = d >> 127;
+                    }
+                    $name::from_limbs_wrapping(a)
+                }
+            }
+
+            /// **Warning**: wraps on overflow.
+            impl Mul for $name {
+                type Output = $name;
+                fn mul(self, rhs: $name) -> $name {
+                    let (a, b) = (self.limbs(), rhs.limbs());
+                    let mut out = [0u64; [<$name Repr>]::LIMBS];
+                    for i in 0..a.len() {
+                        let mut carry = 0u128;
+                        for j in 0..a.len() - i {
+                            let t = out[i + j] as u128 + a[i] as u128 * b[j] as u128 + carry;
+                            out[i + j] = t as u64;
+                            carry = t >> 64;
+                        }
+                    }
+                    $name::from_limbs_wrapping(out)
+                }
+            }
+
+            /// Discards the bits shifted out of the word.
+            ///
+            /// **Warning**: panics if the shift is not below the number of bits.
+            impl Shl<usize> for $name {
+                type Output = $name;
+                fn shl(self, rhs: usize) -> $name {
+                    assert!(
+                        rhs < $name::BITS,
+                        "shift by {} overflows type {}",
+                        rhs,
+                        stringify!($name)
+                    );
+                    self.shl_bits(rhs)
+                }
+            }
+
+            /// **Warning**: panics if the shift is not below the number of bits.
+            impl Shr<usize> for $name {
+                type Output = $name;
+                fn shr(self, rhs: usize) -> $name {
+                    assert!(
+                        rhs < $name::BITS,
+                        "shift by {} overflows type {}",
+                        rhs,
+                        stringify!($name)
+                    );
+                    self.shr_bits(rhs)
+                }
+            }
+
+            impl BitAnd for $name {
+                type Output = $name;
+                fn bitand(self, rhs: $name) -> $name {
+                    self.map_limbs(rhs, |x, y| x & y)
+                }
+            }
+
+            impl BitOr for $name {
+                type Output = $name;
+                fn bitor(self, rhs: $name) -> $name {
+                    self.map_limbs(rhs, |x, y| x | y)
+                }
+            }
+
+            impl BitXor for $name {
+                type Output = $name;
+                fn bitxor(self, rhs: $name) -> $name {
+                    self.map_limbs(rhs, |x, y| x ^ y)
+                }
+            }
+
+            impl Not for $name {
+                type Output = $name;
+                fn not(self) -> $name {
+                    self.map_limbs(self, |x, _| !x)
+                }
+            }
+        }
+    };
+}
+
+/// Defines an index type whose values are the integers below a constant bound:
+///
+/// ```
+/// # use abstract_integers::*;
+/// # use num::{BigUint, CheckedSub, Zero};
+/// # use std::num::ParseIntError;
+/// # use std::ops::*;
+/// define_bounded_index!(Round, 10);
+/// let keys = [0u32; 10];
+/// let k = keys.at(Round::from_literal(3));
+/// ```
+///
+/// With `index::ArrayIndexExt`, the type indexes arrays `[T; N]` through `at` and `at_mut`;
+/// using them on an array shorter than the bound is a compile-time error, so the access is
+/// always in range. The type also indexes slices and arrays with `[]`, with the usual runtime
+/// check.
+#[macro_export]
+macro_rules! define_bounded_index {
+    ($(#[$attr:meta])* $name:ident, $bound:expr) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+        pub struct $name(usize);
+
+        impl $name {
+            /// Values of the type are strictly below this bound.
+            pub const BOUND: usize = $bound;
+
+            /// **Warning**: panics if the literal is not below the bound.
+            #[allow(dead_code)]
+            pub fn from_literal(x: u128) -> Self {
+                if x >= $name::BOUND as u128 {
+                    panic!("literal {} too big for type {}", x, stringify!($name));
+                }
+                $name(x as usize)
+            }
+
+            #[allow(dead_code)]
+            pub fn as_usize(self) -> usize {
+                self.0
+            }
+
+            /// Iterates over all the values of the type in increasing order.
+            #[allow(dead_code)]
+            pub fn all() -> impl Iterator<Item = $name> {
+                (0..$name::BOUND).map($name)
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl $crate::index::BoundedIndex for $name {
+            const BOUND: usize = $name::BOUND;
+
+            fn as_usize(self) -> usize {
+                self.0
+            }
+        }
+
+        impl<T> Index<$name> for [T] {
+            type Output = T;
+            fn index(&self, i: $name) -> &T {
+                &self[i.0]
+            }
+        }
+
+        impl<T> IndexMut<$name> for [T] {
+            fn index_mut(&mut self, i: $name) -> &mut T {
+                &mut self[i.0]
+            }
+        }
+    };
+}
+
+/// Defines a bounded natural integer with modular arithmetic operations
+///
+/// By default, `/` is the floor division of the representatives, reduced modulo the modulus.
+/// With the `field_div` option after the modulus, `/` is the field division, multiplying by the
+/// inverse of the divisor, which is only meaningful for prime moduli. Both are always available
+/// as the `div_floor` and `div_field` methods.
+///
+/// With the `tables` option, a modulus below 2^16 is handled with native integers and
+/// precomputed multiplication and inversion tables, see `tables`. The lookups depend on the
+/// values, so the option is rejected on secret types. A modulus written as
+/// `Base::from_literal(m)` that is not between 2 and 2^16 is rejected at compile time; other
+/// moduli are only known at runtime, and the first operation panics if they are too big.
+///
+/// The `no_div` option removes the `/` and `%` operators, and the `no_ord` option removes the
+/// comparisons with `<` and the other ordering operators, which are seldom meaningful for field
+/// elements. Using them is then a compile-time error.
+///
+/// A refined type over a base defined with the `secret` option must be defined with the `secret`
+/// option too. Division, remainder, `inv`, and the `pow` methods other than `pow_ct`, which run
+/// in variable time, are then only available on the view returned by `var_time()`, see
+/// `vartime`. The `inv` and `pow` of `ModularInteger` use `inv_ct` and `pow_ct` instead.
+///
+/// Like `define_abstract_integer_checked`, the definition can be wrapped in a public module with
+/// `define_refined_modular_integer!(mod name { ... })`, and attributes written before the name of
+/// the type are attached to the generated struct.
+///
+/// A block of named constants can also be given after the options, see
+/// `define_abstract_integer_checked`. The constants are checked at compile time against the bits
+/// of the base, and against the modulus when it is written as `Base::from_literal(m)`. Other
+/// moduli are only known at runtime: a constant that is not below them fails the invariant check
+/// of the first operation using it in debug builds.
+#[macro_export]
+macro_rules! define_refined_modular_integer {
+    (mod $module:ident { $($args:tt)* }) => {
+        pub mod $module {
+            #[allow(unused_imports)]
+            use super::*;
+            $crate::define_refined_modular_integer!($($args)*);
+        }
+    };
+    (
+        $(#[$attr:meta])* $name:ident, $base:ident, $mbase:ident::from_literal($m:literal)
+        $(, $opt:ident)* $(,)?
+        $(; constants { $($cname:ident = $cval:expr),* $(,)? })?
+    ) => {
+        $crate::define_refined_modular_integer!(
+            @bound Some($m);
+            $(#[$attr])* $name, $base, $mbase::from_literal($m) $(, $opt)*
+            $(; constants { $($cname = $cval),* })?
+        );
+    };
+    (
+        $(#[$attr:meta])* $name:ident, $base:ident, $max:expr $(, $opt:ident)* $(,)?
+        $(; constants { $($cname:ident = $cval:expr),* $(,)? })?
+    ) => {
+        $crate::define_refined_modular_integer!(
+            @bound None;
+            $(#[$attr])* $name, $base, $max $(, $opt)*
+            $(; constants { $($cname = $cval),* })?
+        );
+    };
+    (
+        @bound $bound:expr;
+        $(#[$attr:meta])* $name:ident, $base:ident, $max:expr $(, $opt:ident)* $(,)?
+        $(; constants { $($cname:ident = $cval:expr),* $(,)? })?
+    ) => {
+        $crate::__abstract_integer_options!(@check $($opt)*);
+        const _: () = assert!(
+            $base::SECRET == $crate::__abstract_integer_options!(@has secret; $($opt)*),
+            concat!(
+                "`",
+                stringify!($name),
+                "` must be defined with the `secret` option exactly when its base is secret"
+            )
+        );
+        $crate::__abstract_integer_options!(@if tables; $($opt)*; {
+            $crate::__abstract_integer_options!(@if secret; $($opt)*; {
+                compile_error!("the lookups of the `tables` option would leak secret values");
+            });
+            const _: () = assert!(
+                match $name::__LITERAL_BOUND {
+                    Some(m) => 2 <= m && m < $crate::tables::MODULUS_BOUND as u128,
+                    None => true,
+                },
+                concat!(
+                    "the modulus of `",
+                    stringify!($name),
+                    "` is not between 2 and 2^16 for the `tables` option"
+                )
+            );
+        });
+
+        $($(
+            impl $name {
+                pub const $cname: $name = $name::__from_u128_const($cval);
+            }
+
+            const _: $name = $name::$cname;
+        )*)?
+
+        $(#[$attr])*
+        #[derive(Clone, Copy, PartialEq, Eq, Default)]
+        #[repr(transparent)]
+        pub struct $name($base);
+
         impl $name {
+            /// The modulus when it is given as a literal, checked by `nat!`.
+            #[doc(hidden)]
+            pub const __LITERAL_BOUND: Option<u128> = $bound;
+
+            /// Builds a value at compile time, for the constants blocks.
+            #[doc(hidden)]
+            pub const fn __from_u128_const(x: u128) -> Self {
+                assert!(
+                    match $name::__LITERAL_BOUND {
+                        Some(bound) => x < bound,
+                        None => true,
+                    },
+                    concat!("constant not below the modulus of type ", stringify!($name))
+                );
+                $name($base::__from_u128_const(x))
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                let uint: $base = (*self).into();
+                write!(f, "{}", uint)
+            }
+        }
+
+        /// With `{:#?}`, prints the type name, the modulus and the value in hexadecimal and
+        /// decimal, the value staying redacted for secret bases.
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                let uint: $base = (*self).into();
+                if !f.alternate() {
+                    return write!(f, "{}", uint);
+                }
+                let mut s = f.debug_struct(stringify!($name));
+                s.field("modulus", &format_args!("{:#x}", BigUint::from($name::max())));
+                if $base::SECRET {
+                    s.field("value", &format_args!("<redacted>"));
+                } else {
+                    let uint: BigUint = uint.into();
+                    s.field("hex", &format_args!("{:#x}", uint));
+                    s.field("decimal", &format_args!("{}", uint));
+                }
+                s.finish()
+            }
+        }
+
+        impl $name {
+            /// Number of bits of the representation, inherited from the base type.
+            pub const BITS: usize = $base::BITS;
+
+            /// Number of bytes of the canonical encoding, inherited from the base type.
+            pub const BYTES: usize = $base::BYTES;
+
+            /// Whether the type is secret, like its base.
+            pub const SECRET: bool = $base::SECRET;
+
             pub fn max() -> $base {
                 $max
             }
 
+            /// Returns the modulus of the type as a value of the base type, same as `max`.
+            #[allow(dead_code)]
+            pub fn modulus() -> $base {
+                $name::max()
+            }
+
+            const TABLES: bool = $crate::__abstract_integer_options!(@has tables; $($opt)*);
+
+            /// Operation tables of the type, built on first use, see `tables`. Only used by the
+            /// operations of types defined with the `tables` option.
+            ///
+            /// **Warning**: panics if the modulus does not fit in 16 bits.
+            #[allow(dead_code)]
+            pub fn tables() -> &'static $crate::tables::OpTables {
+                static TABLES: std::sync::OnceLock<$crate::tables::OpTables> =
+                    std::sync::OnceLock::new();
+                TABLES.get_or_init(|| $crate::tables::OpTables::new($name::reducer().modulus()))
+            }
+
+            /// Inversion table of the moduli below `tables::INV_TABLE_BOUND`, built on the first
+            /// inversion. Secret bases never use it, the lookup would leak the value through the
+            /// cache.
+            fn inverse_table() -> Option<&'static [u16]> {
+                static TABLE: std::sync::OnceLock<Option<Vec<u16>>> = std::sync::OnceLock::new();
+                TABLE
+                    .get_or_init(|| {
+                        let m = $name::reducer().modulus();
+                        let small = !$base::SECRET
+                            && *m >= BigUint::from(2u32)
+                            && *m < BigUint::from($crate::tables::INV_TABLE_BOUND);
+                        if small {
+                            Some($crate::tables::inverse_table(m))
+                        } else {
+                            None
+                        }
+                    })
+                    .as_deref()
+            }
+
+            fn table_op(
+                self,
+                rhs: $name,
+                op: fn(&$crate::tables::OpTables, u16, u16) -> u16,
+            ) -> $name {
+                let r = op($name::tables(), self.0.__low_u64() as u16, rhs.0.__low_u64() as u16);
+                $name($base::__from_u64(r as u64))
+            }
+
+            /// Reducer of the type, detecting the shape of the modulus on first use to select a
+            /// fast reduction path, see `reduction`. The modulus expression is evaluated once.
+            #[allow(dead_code)]
+            pub fn reducer() -> &'static $crate::reduction::Reducer {
+                static REDUCER: std::sync::OnceLock<$crate::reduction::Reducer> =
+                    std::sync::OnceLock::new();
+                REDUCER.get_or_init(|| $crate::reduction::Reducer::new($name::max().into()))
+            }
+
+            /// Returns `true` if the stored value is strictly below the modulus. All the
+            /// constructors and operations of the type guarantee it, so this can only be false
+            /// for values forged by unsafe code.
+            #[allow(dead_code)]
+            pub fn is_canonical(&self) -> bool {
+                self.0 < Self::max()
+            }
+
+            /// In debug builds, checks that the value is below the modulus. Every operation goes
+            /// through this check, so that values corrupted by unsafe code are detected early.
+            #[inline]
+            fn check_invariant(&self) {
+                debug_assert!(
+                    self.is_canonical(),
+                    "invariant violated: value not reduced modulo the modulus of type {}",
+                    stringify!($name)
+                );
+            }
+
+            #[allow(dead_code)]
+            pub fn from_hex(s: &str) -> Self {
+                $base::from_hex(s).into()
+            }
+
+            #[allow(dead_code)]
+            fn inv_vartime(self) -> Self {
+                if $name::TABLES {
+                    return self.table_op(self, |t, a, _| t.inv(a));
+                }
+                if let Some(table) = $name::inverse_table() {
+                    return $name($base::__from_u64(table[self.0.__low_u64() as usize] as u64));
+                }
+                let m: BigUint = Self::max().into();
+                let s: BigUint = self.into();
+                s.modpow(&(&m - BigUint::from(2u32)), &m).into()
+            }
+
+            /// Multiplicative inverse for prime moduli, computed as `self^(p-2)` with the same
+            /// sequence of multiplications and squarings for all the values of `self`: the
+            /// branches only depend on the bits of the public exponent. Note that the underlying
+            /// `BigUint` arithmetic is not itself constant-time.
+            #[allow(dead_code)]
+            pub fn inv_ct(self) -> Self {
+                let m: BigUint = Self::max().into();
+                let e = &m - BigUint::from(2u32);
+                let mut r = <$name as $crate::traits::ModularInteger>::one();
+                for i in (0..m.bits()).rev() {
+                    r = r.square();
+                    let t = r * self;
+                    if (&e >> i) & BigUint::from(1u32) == BigUint::from(1u32) {
+                        r = t;
+                    }
+                }
+                r
+            }
+
+            #[allow(dead_code)]
+            fn pow_big_vartime(self, exp: &BigUint) -> Self {
+                let m: BigUint = Self::max().into();
+                let s: BigUint = self.into();
+                s.modpow(exp, &m).into()
+            }
+
+            #[allow(dead_code)]
+            fn pow_mod_signed_vartime(self, exp: i128) -> Self {
+                if exp < 0 {
+                    let one = <$name as $crate::traits::ModularInteger>::one();
+                    let x = one.div_field_vartime(self);
+                    x.pow_big_vartime(&BigUint::from(exp.unsigned_abs()))
+                } else {
+                    self.pow_big_vartime(&BigUint::from(exp as u128))
+                }
+            }
+
+            /// Returns the sum of the products `a[i] * b[i]`, reducing only once at the end.
+            ///
+            /// **Warning**: panics if the slices have different lengths.
+            #[allow(dead_code)]
+            pub fn inner_product(a: &[Self], b: &[Self]) -> Self {
+                assert_eq!(a.len(), b.len(), "inner product of slices of different lengths");
+                let sum = a.iter().zip(b.iter()).fold(BigUint::from(0u32), |acc, (x, y)| {
+                    acc + BigUint::from(*x) * BigUint::from(*y)
+                });
+                let max: BigUint = $max.into();
+                let d: $base = (sum % max).into();
+                d.into()
+            }
+
+            /// Returns the sum of the elements, reducing only once at the end.
+            #[allow(dead_code)]
+            pub fn sum_mod(xs: &[Self]) -> Self {
+                let sum = xs.iter().fold(BigUint::from(0u32), |acc, x| acc + BigUint::from(*x));
+                let d: $base = $name::reducer().reduce(sum).into();
+                $name(d)
+            }
+
+            /// Returns the product of the elements, reducing the intermediate product only when
+            /// it exceeds the bits of a product of two elements by more than
+            /// `accumulator::DEFAULT_HEADROOM` bits.
+            #[allow(dead_code)]
+            pub fn product_mod(xs: &[Self]) -> Self {
+                let reducer = $name::reducer();
+                let limit = 2 * reducer.modulus().bits() + $crate::accumulator::DEFAULT_HEADROOM;
+                let mut product = BigUint::from(1u32);
+                for x in xs {
+                    product *= BigUint::from(*x);
+                    if product.bits() > limit {
+                        product = reducer.reduce(product);
+                    }
+                }
+                let d: $base = reducer.reduce(product).into();
+                $name(d)
+            }
+
+            /// Returns `self * self`, converting the operand only once, through the tables with
+            /// the `tables` option.
+            #[allow(dead_code)]
+            pub fn square(self) -> Self {
+                if $name::TABLES {
+                    return self.table_op(self, $crate::tables::OpTables::mul);
+                }
+                let a: BigUint = self.into();
+                let d: $base = $name::reducer().reduce(&a * &a).into();
+                let r = $name(d);
+                $crate::shadow::check_modular(stringify!($name), "*", self, self, r);
+                r
+            }
+
+            /// Returns the nearest `f64` to the representative of the value, or infinity if it
+            /// exceeds the range of `f64`.
+            #[allow(dead_code)]
+            pub fn to_f64_lossy(self) -> f64 {
+                self.0.to_f64_lossy()
+            }
+
+            /// Converts a float holding an integer below the modulus. Larger integers are
+            /// rejected rather than reduced.
+            #[allow(dead_code)]
+            pub fn try_from_f64(x: f64) -> Result<Self, $crate::diagnostics::FromFloatError> {
+                let bound = BigUint::from($name::max());
+                $crate::__f64_to_biguint(x, stringify!($name), $name::SECRET, bound)
+                    .map(|x| $name(x.into()))
+            }
+
+            /// Returns `2 * self`, computed as an addition.
+            #[allow(dead_code)]
+            pub fn double(self) -> Self {
+                self + self
+            }
+
+            /// Returns `3 * self`, computed with two additions.
+            #[allow(dead_code)]
+            pub fn triple(self) -> Self {
+                self.double() + self
+            }
+
+            /// Returns `k * self`, computed with doublings and additions instead of a full
+            /// multiplication.
+            #[allow(dead_code)]
+            pub fn mul_small(self, k: u64) -> Self {
+                let mut acc = <$name as $crate::traits::ModularInteger>::zero();
+                for i in (0..64 - k.leading_zeros()).rev() {
+                    acc = acc.double();
+                    if (k >> i) & 1 == 1 {
+                        acc = acc + self;
+                    }
+                }
+                acc
+            }
+
+            /// Returns `self * b + c` with a single reduction.
+            #[allow(dead_code)]
+            pub fn mul_add(self, b: Self, c: Self) -> Self {
+                if $name::TABLES {
+                    return self.table_op(b, $crate::tables::OpTables::mul) + c;
+                }
+                let a: BigUint = self.into();
+                let b: BigUint = b.into();
+                let c: BigUint = c.into();
+                let d: $base = $name::reducer().reduce(a * b + c).into();
+                $name(d)
+            }
+
+            /// Returns the iterator over `1, self, self^2, ...`, see `exponentiation::powers`.
+            #[allow(dead_code)]
+            pub fn powers(self) -> $crate::exponentiation::Powers<Self> {
+                $crate::exponentiation::powers(self)
+            }
+
+            /// Evaluates the polynomial `coeffs[0] + coeffs[1] * x + coeffs[2] * x^2 + ...` at
+            /// `x` with Horner's rule, see `polynomial::horner`.
+            #[allow(dead_code)]
+            pub fn horner(coeffs: &[Self], x: Self) -> Self {
+                $crate::polynomial::horner(coeffs, x)
+            }
+
+            fn zip_slices<F: Fn(BigUint, BigUint, &BigUint) -> BigUint>(
+                dst: &mut [Self],
+                a: &[Self],
+                b: &[Self],
+                f: F,
+            ) {
+                assert!(
+                    dst.len() == a.len() && a.len() == b.len(),
+                    "element-wise operation on slices of different lengths"
+                );
+                let max: BigUint = $max.into();
+                for ((d, x), y) in dst.iter_mut().zip(a.iter()).zip(b.iter()) {
+                    let r: $base = f(BigUint::from(*x), BigUint::from(*y), &max).into();
+                    *d = r.into();
+                }
+            }
+
+            /// Sets `dst[i] = a[i] + b[i]`, evaluating the modulus once for the whole slices.
+            ///
+            /// **Warning**: panics if the slices have different lengths.
+            #[allow(dead_code)]
+            pub fn add_slices(dst: &mut [Self], a: &[Self], b: &[Self]) {
+                Self::zip_slices(dst, a, b, |x, y, m| (x + y) % m)
+            }
+
+            /// Sets `dst[i] = a[i] - b[i]`, evaluating the modulus once for the whole slices.
+            ///
+            /// **Warning**: panics if the slices have different lengths.
+            #[allow(dead_code)]
+            pub fn sub_slices(dst: &mut [Self], a: &[Self], b: &[Self]) {
+                Self::zip_slices(dst, a, b, |x, y, m| (x + m - y) % m)
+            }
+
+            /// Sets `dst[i] = a[i] * b[i]`, evaluating the modulus once for the whole slices.
+            ///
+            /// **Warning**: panics if the slices have different lengths.
+            #[allow(dead_code)]
+            pub fn mul_slices(dst: &mut [Self], a: &[Self], b: &[Self]) {
+                Self::zip_slices(dst, a, b, |x, y, m| x * y % m)
+            }
+
+            /// Sets `dst[i] = k * a[i]`, evaluating the modulus once for the whole slices.
+            ///
+            /// **Warning**: panics if the slices have different lengths.
+            #[allow(dead_code)]
+            pub fn scale_slice(dst: &mut [Self], a: &[Self], k: Self) {
+                assert_eq!(dst.len(), a.len(), "element-wise operation on slices of different lengths");
+                let max: BigUint = $max.into();
+                let k = BigUint::from(k);
+                for (d, x) in dst.iter_mut().zip(a.iter()) {
+                    let r: $base = (&k * BigUint::from(*x) % &max).into();
+                    *d = r.into();
+                }
+            }
+
+            /// Montgomery radix `R = 2^(64 * LIMBS)`, with `LIMBS` the number of 64-bit limbs
+            /// of the base type, reduced modulo the modulus, and its inverse.
+            ///
+            /// **Warning**: panics if the modulus is even.
+            fn montgomery_radix() -> (BigUint, BigUint) {
+                let max: BigUint = $max.into();
+                assert!(&max % 2u32 == BigUint::from(1u32), "Montgomery form requires an odd modulus");
+                let k = 64 * $base::LIMBS;
+                let r = (BigUint::from(1u32) << k) % &max;
+                let half: BigUint = (&max + 1u32) >> 1;
+                let r_inv = half.modpow(&BigUint::from(k), &max);
+                (r, r_inv)
+            }
+
+            /// Sets `dst[i]` to the Montgomery form `src[i] * R` of `src[i]`, see
+            /// `montgomery_radix`. The radix is computed once for the whole slice.
+            ///
+            /// **Warning**: panics if the slices have different lengths or if the modulus is even.
+            #[allow(dead_code)]
+            pub fn to_montgomery_slice(dst: &mut [Self], src: &[Self]) {
+                assert_eq!(dst.len(), src.len(), "element-wise operation on slices of different lengths");
+                let max: BigUint = $max.into();
+                let (r, _) = Self::montgomery_radix();
+                for (d, x) in dst.iter_mut().zip(src.iter()) {
+                    let y: $base = (BigUint::from(*x) * &r % &max).into();
+                    *d = y.into();
+                }
+            }
+
+            /// Sets `dst[i]` to the value `src[i] * R^-1` whose Montgomery form is `src[i]`.
+            ///
+            /// **Warning**: panics if the slices have different lengths or if the modulus is even.
+            #[allow(dead_code)]
+            pub fn from_montgomery_slice(dst: &mut [Self], src: &[Self]) {
+                assert_eq!(dst.len(), src.len(), "element-wise operation on slices of different lengths");
+                let max: BigUint = $max.into();
+                let (_, r_inv) = Self::montgomery_radix();
+                for (d, x) in dst.iter_mut().zip(src.iter()) {
+                    let y: $base = (BigUint::from(*x) * &r_inv % &max).into();
+                    *d = y.into();
+                }
+            }
+
+            /// Returns self to the power of the argument using a Montgomery ladder, see the
+            /// base type's `pow_ct`.
+            #[allow(dead_code)]
+            pub fn pow_ct(self, exp: u128) -> Self {
+                let base: $base = self.into();
+                base.pow_ct(exp, Self::max()).into()
+            }
+
+            #[allow(dead_code)]
+            pub fn from_bytes_le(v: &[u8]) -> Self {
+                $base::from_bytes_le(v).into()
+            }
+
+            #[allow(dead_code)]
+            pub fn to_bytes_le(self) -> Vec<u8> {
+                $base::to_bytes_le(self.into())
+            }
+
+            /// Reads a value encoded in the canonical byte order of the base type. The value is
+            /// reduced modulo the modulus.
+            #[allow(dead_code)]
+            pub fn from_bytes(v: &[u8]) -> Self {
+                $base::from_bytes(v).into()
+            }
+
+            /// Reads a value like `from_bytes`, but returns `None` instead of panicking if it does
+            /// not fit in the base type.
+            #[allow(dead_code)]
+            pub fn try_from_bytes(v: &[u8]) -> Option<Self> {
+                $base::try_from_bytes(v).map(Into::into)
+            }
+
+            /// Reads an integer of any length encoded in the canonical byte order of the base
+            /// type, and reduces it modulo the modulus. Used to turn hash digests into elements.
+            #[allow(dead_code)]
+            pub fn from_bytes_mod_order(v: &[u8]) -> Self {
+                if $base::LITTLE_ENDIAN {
+                    Self::from_bytes_le_mod_order(v)
+                } else {
+                    Self::from_bytes_be_mod_order(v)
+                }
+            }
+
+            /// Reduces a value of the double-width companion of the base type, such as a product
+            /// returned by `mul_wide`, with the reduction selected for the shape of the modulus,
+            /// see `reducer`.
+            #[allow(dead_code)]
+            pub fn reduce_wide<W>(x: W) -> Self
+            where
+                W: $crate::traits::DoubleWidth<Narrow = $base>,
+            {
+                let d: $base = $name::reducer().reduce(x.into()).into();
+                d.into()
+            }
+
+            /// Reads a little-endian integer of any length and reduces it modulo the modulus.
+            #[allow(dead_code)]
+            pub fn from_bytes_le_mod_order(v: &[u8]) -> Self {
+                BigUint::from_bytes_le(v).into()
+            }
+
+            /// Reads a big-endian integer of any length and reduces it modulo the modulus.
+            #[allow(dead_code)]
+            pub fn from_bytes_be_mod_order(v: &[u8]) -> Self {
+                BigUint::from_bytes_be(v).into()
+            }
+
+            /// Returns the big-endian encoding of the value without leading zeros, see the base
+            /// type's `to_bytes_minimal`.
+            #[allow(dead_code)]
+            pub fn to_bytes_minimal(self) -> Vec<u8> {
+                self.0.to_bytes_minimal()
+            }
+
+            /// Reads a value from its minimal big-endian encoding.
+            ///
+            /// **Warning**: panics if the encoding has leading zeros or if the value is not
+            /// below the modulus.
+            #[allow(dead_code)]
+            pub fn from_bytes_minimal(v: &[u8]) -> Self {
+                let x = $base::from_bytes_minimal(v);
+                if x >= Self::max() {
+                    let x = $crate::__redacted(x, $name::SECRET);
+                    panic!("{} is not below the modulus of type {}", x, stringify!($name));
+                }
+                x.into()
+            }
+
+            /// Returns the value as 64-bit limbs, least significant limb first.
+            #[allow(dead_code)]
+            pub fn as_limbs(&self) -> [u64; $base::LIMBS] {
+                self.0.as_limbs()
+            }
+
+            /// Builds a value from 64-bit limbs, least significant limb first. The value is
+            /// reduced modulo the modulus.
+            #[allow(dead_code)]
+            pub fn from_limbs(limbs: [u64; $base::LIMBS]) -> Self {
+                $base::from_limbs(limbs).into()
+            }
+
+            /// Gets the `i`-th least significant bit of this integer.
             #[allow(dead_code)]
-            pub fn from_hex(s: &str) -> Self {
-                $base::from_hex(s).into()
+            pub fn bit(self, i: usize) -> bool {
+                $base::bit(self.into(), i)
             }
 
+            /// Returns the number of trailing zero bits of the canonical representative.
             #[allow(dead_code)]
-            pub fn inv(self) -> Self {
-                let base: $base = self.into();
-                base.inv(Self::max()).into()
+            pub fn trailing_zeros(self) -> usize {
+                $base::trailing_zeros(self.into())
             }
 
+            /// Gets the least significant bit of the canonical representative.
             #[allow(dead_code)]
-            pub fn pow_felem(self, exp: Self) -> Self {
-                let base: $base = self.into();
-                base.pow_felem(exp.into(), Self::max()).into()
+            pub fn lsb(self) -> bool {
+                $base::lsb(self.into())
             }
-            /// Returns self to the power of the argument.
-            /// The exponent is a u128.
+
             #[allow(dead_code)]
-            pub fn pow(self, exp: u128) -> Self {
-                let base: $base = self.into();
-                base.pow(exp, Self::max()).into()
+            pub fn is_odd(self) -> bool {
+                self.lsb()
             }
 
             #[allow(dead_code)]
-            pub fn from_bytes_le(v: &[u8]) -> Self {
-                $base::from_bytes_le(v).into()
+            pub fn is_even(self) -> bool {
+                !self.lsb()
             }
 
+            /// Returns the non-adjacent form of the canonical value, see `recoding`.
             #[allow(dead_code)]
-            pub fn to_bytes_le(self) -> Vec<u8> {
-                $base::to_bytes_le(self.into())
+            pub fn to_naf(self) -> Vec<i8> {
+                $crate::recoding::wnaf(&self.into(), 2)
             }
 
-            /// Gets the `i`-th least significant bit of this integer.
+            /// Returns the width-`w` non-adjacent form of the canonical value, see `recoding`.
+            ///
+            /// **Warning**: panics if `w` is not between 2 and 8.
             #[allow(dead_code)]
-            pub fn bit(self, i: usize) -> bool {
-                $base::bit(self.into(), i)
+            pub fn to_wnaf(self, w: usize) -> Vec<i8> {
+                $crate::recoding::wnaf(&self.into(), w)
+            }
+
+            /// Returns the signed fixed-window recoding of width `w` of the canonical value, with a
+            /// number of digits depending only on the type, see `recoding::signed_digits_ct`.
+            ///
+            /// **Warning**: panics if `w` is not between 2 and 8.
+            #[allow(dead_code)]
+            pub fn to_signed_digits_ct(self, w: usize) -> Vec<i8> {
+                $crate::recoding::signed_digits_ct(&self.as_limbs(), $base::BITS, w)
+            }
+
+            /// Returns the Jacobi symbol of the element modulo the modulus, the Legendre symbol
+            /// for prime moduli, see `number_theory::jacobi`.
+            ///
+            /// **Warning**: panics if the modulus is even.
+            #[allow(dead_code)]
+            pub fn jacobi(self) -> i8 {
+                $crate::number_theory::jacobi(&self.into(), $name::reducer().modulus())
+            }
+
+            /// Returns the smallest `x` below `bound` such that `self^x = target`, or `None` if
+            /// there is none, see `number_theory::discrete_log`. Only meant for small fields and
+            /// tests, the work grows with the square root of `bound`.
+            #[allow(dead_code)]
+            pub fn discrete_log(self, target: Self, bound: u64) -> Option<u64> {
+                $crate::number_theory::discrete_log(
+                    &self.into(),
+                    &target.into(),
+                    $name::reducer().modulus(),
+                    bound,
+                )
+            }
+
+            /// Returns the multiplicative order of this element. `factors` is the factorization
+            /// of the order of the multiplicative group (`p - 1` for a prime modulus `p`) as a
+            /// list of `(prime, exponent)` pairs.
+            ///
+            /// **Warning**: panics if the element is not invertible.
+            #[allow(dead_code)]
+            pub fn order(self, factors: &[($base, u32)]) -> $base {
+                let factors: Vec<(BigUint, u32)> =
+                    factors.iter().map(|(q, e)| ((*q).into(), *e)).collect();
+                let x: $base = self.into();
+                $crate::number_theory::multiplicative_order(
+                    &x.into(),
+                    &Self::max().into(),
+                    &factors,
+                )
+                .into()
+            }
+
+            /// Returns `true` if this element generates the whole multiplicative group, whose
+            /// order is given by its factorization as in `order`.
+            #[allow(dead_code)]
+            pub fn is_generator(self, factors: &[($base, u32)]) -> bool {
+                let factors: Vec<(BigUint, u32)> =
+                    factors.iter().map(|(q, e)| ((*q).into(), *e)).collect();
+                let x: $base = self.into();
+                $crate::number_theory::is_generator(&x.into(), &Self::max().into(), &factors)
+            }
+
+            /// Returns the smallest primitive root of the field. `p - 1` is factored with
+            /// `number_theory::factor`, which is best effort: use `primitive_root_with_factors`
+            /// for big fields whose `p - 1` has several large prime factors.
+            ///
+            /// **Warning**: the modulus must be prime.
+            #[allow(dead_code)]
+            pub fn primitive_root() -> Self {
+                let p: BigUint = Self::max().into();
+                let factors = $crate::number_theory::factor(&(&p - 1u32));
+                let g: $base = $crate::number_theory::primitive_root(&p, &factors).into();
+                g.into()
+            }
+
+            /// Returns the smallest primitive root of the field, given the factorization of
+            /// `p - 1` as a list of `(prime, exponent)` pairs.
+            ///
+            /// **Warning**: the modulus must be prime.
+            #[allow(dead_code)]
+            pub fn primitive_root_with_factors(factors: &[($base, u32)]) -> Self {
+                let factors: Vec<(BigUint, u32)> =
+                    factors.iter().map(|(q, e)| ((*q).into(), *e)).collect();
+                let g: $base =
+                    $crate::number_theory::primitive_root(&Self::max().into(), &factors).into();
+                g.into()
+            }
+
+            /// Returns a primitive `n`-th root of unity, derived from `primitive_root`.
+            ///
+            /// **Warning**: the modulus must be prime, panics if `n` does not divide `p - 1`.
+            #[allow(dead_code)]
+            pub fn root_of_unity(n: u128) -> Self {
+                let p: BigUint = Self::max().into();
+                let factors = $crate::number_theory::factor(&(&p - 1u32));
+                let w: $base =
+                    $crate::number_theory::root_of_unity(&BigUint::from(n), &p, &factors).into();
+                w.into()
+            }
+
+            /// Returns a primitive `n`-th root of unity, derived from
+            /// `primitive_root_with_factors`.
+            ///
+            /// **Warning**: the modulus must be prime, panics if `n` does not divide `p - 1`.
+            #[allow(dead_code)]
+            pub fn root_of_unity_with_factors(n: u128, factors: &[($base, u32)]) -> Self {
+                let factors: Vec<(BigUint, u32)> =
+                    factors.iter().map(|(q, e)| ((*q).into(), *e)).collect();
+                let w: $base = $crate::number_theory::root_of_unity(
+                    &BigUint::from(n),
+                    &Self::max().into(),
+                    &factors,
+                )
+                .into();
+                w.into()
             }
 
             #[allow(dead_code)]
             pub fn from_literal(x: u128) -> Self {
                 let big_x = BigUint::from(x);
-                if big_x > $name::max().into() {
+                if big_x >= $name::max().into() {
+                    let x = $crate::__redacted(x, $base::SECRET);
                     panic!("literal {} too big for type {}", x, stringify!($name));
                 }
                 $name(big_x.into())
             }
 
+            #[doc(hidden)]
+            pub fn __from_nat_literal(s: &str) -> Self {
+                let x = $crate::__parse_literal(s);
+                if x >= $name::max().into() {
+                    panic!("literal {} too big for type {}", s, stringify!($name));
+                }
+                $name(x.into())
+            }
+
+            /// Maps signed literals to their class modulo the modulus, so that constants like `-1`
+            /// or `-3` can be written directly.
             #[allow(dead_code)]
             pub fn from_signed_literal(x: i128) -> Self {
-                let big_x = BigUint::from(x as u128);
-                if big_x > $name::max().into() {
-                    panic!("literal {} too big for type {}", x, stringify!($name));
+                let max: BigUint = $name::max().into();
+                let big_x = BigUint::from(x.unsigned_abs()) % &max;
+                if x < 0 && big_x != BigUint::from(0u32) {
+                    let d: $base = (max - big_x).into();
+                    return d.into();
                 }
                 $name(big_x.into())
             }
+
+            /// Returns `self + 1` modulo the modulus.
+            #[allow(dead_code)]
+            pub fn inc(self) -> Self {
+                self + <$name as $crate::traits::ModularInteger>::one()
+            }
+
+            /// Returns `self - 1` modulo the modulus.
+            #[allow(dead_code)]
+            pub fn dec(self) -> Self {
+                self - <$name as $crate::traits::ModularInteger>::one()
+            }
+
+            /// Converts to another refined type over the same base, reducing the representative
+            /// by the modulus of the target type, e.g. from a base field to a scalar ring.
+            #[allow(dead_code)]
+            pub fn reduce_into<T: From<$base>>(self) -> T {
+                T::from(self.0)
+            }
+        }
+
+        impl $crate::traits::Exponent for $name {}
+
+        impl $crate::traits::ModularInteger for $name {
+            const FIELD_DIVISION: bool = $name::FIELD_DIV;
+            const BITS: usize = $name::BITS;
+            const BYTES: usize = $name::BYTES;
+            const SECRET: bool = $name::SECRET;
+
+            fn zero() -> Self {
+                $base::from_literal(0).into()
+            }
+
+            fn one() -> Self {
+                $base::from_literal(1).into()
+            }
+
+            fn modulus() -> BigUint {
+                $name::max().into()
+            }
+
+            /// Constant-time for secret bases, see `inv_ct`.
+            fn inv(self) -> Self {
+                if $base::SECRET {
+                    self.inv_ct()
+                } else {
+                    self.inv_vartime()
+                }
+            }
+
+            /// Constant-time for secret bases, see `pow_ct`.
+            fn pow(self, exp: u128) -> Self {
+                if $base::SECRET {
+                    self.pow_ct(exp)
+                } else {
+                    self.pow_big_vartime(&BigUint::from(exp))
+                }
+            }
+
+            fn square(self) -> Self {
+                $name::square(self)
+            }
         }
 
+        $crate::__abstract_integer_from_primitives!($name, u8 u16 u32 u64 usize);
+        $crate::__abstract_integer_serde!($name, $name::BYTES, |x| {
+            if x < $name::max().into() {
+                let x: $base = x.into();
+                Some(x.into())
+            } else {
+                None
+            }
+        });
+        $crate::__abstract_integer_schema!($name, BigUint::from($name::max()) - 1u32);
+        $crate::__abstract_integer_register!(
+            $name,
+            $base::BITS,
+            Some(stringify!($base)),
+            Some(|| BigUint::from($name::max()))
+        );
+        $crate::__abstract_integer_options!(@unless secret; $($opt)*; {
+            $crate::__abstract_integer_any!($name);
+        });
+        $crate::__abstract_integer_xof!($name);
+        $crate::__abstract_integer_rfc6979!($name);
+        $crate::__abstract_integer_rlp!($name, |x| {
+            if x < $name::max().into() {
+                let x: $base = x.into();
+                Some(x.into())
+            } else {
+                None
+            }
+        });
+        $crate::__abstract_integer_cbor!($name, |x| {
+            if x < $name::max().into() {
+                let x: $base = x.into();
+                Some(x.into())
+            } else {
+                None
+            }
+        });
+        $crate::__abstract_integer_prost!($name, |x| {
+            if x < $name::max().into() {
+                let x: $base = x.into();
+                Some(x.into())
+            } else {
+                None
+            }
+        });
+        $crate::__abstract_integer_ct_eq!($name, |a, b| {
+            $crate::subtle::ConstantTimeEq::ct_eq(&a.0, &b.0)
+        });
+        $crate::__abstract_integer_generic_array!(
+            $name,
+            $name::BYTES,
+            |x| x.0.into_bytes(),
+            |a| $name::from($base::from_bytes(&a))
+        );
+
+        /// Reduces the base value modulo the modulus, so that any base value gives a canonical
+        /// element.
         impl From<$base> for $name {
             fn from(x: $base) -> $name {
-                $name(x % $max)
+                let out = $name($name::reducer().reduce(x.into()).into());
+                out.check_invariant();
+                out
             }
         }
 
-        impl Into<$base> for $name {
-            fn into(self) -> $base {
-                self.0
+        /// Compares the canonical value with a value of the base type.
+        impl PartialEq<$base> for $name {
+            fn eq(&self, other: &$base) -> bool {
+                self.0 == *other
+            }
+        }
+
+        $crate::__abstract_integer_options!(@unless no_ord; $($opt)*; {
+            impl PartialOrd for $name {
+                fn partial_cmp(&self, other: &$name) -> Option<std::cmp::Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            /// Orders the canonical values.
+            impl Ord for $name {
+                fn cmp(&self, other: &$name) -> std::cmp::Ordering {
+                    self.0.cmp(&other.0)
+                }
+            }
+
+            /// Compares the canonical value with a value of the base type.
+            impl PartialOrd<$base> for $name {
+                fn partial_cmp(&self, other: &$base) -> Option<std::cmp::Ordering> {
+                    Some(self.0.cmp(other))
+                }
+            }
+        });
+
+        impl From<$name> for $base {
+            fn from(x: $name) -> $base {
+                x.check_invariant();
+                x.0
+            }
+        }
+
+        impl From<$name> for BigUint {
+            fn from(x: $name) -> BigUint {
+                let x: $base = x.into();
+                x.into()
+            }
+        }
+
+        /// Reduces the value modulo the modulus.
+        impl From<BigUint> for $name {
+            fn from(x: BigUint) -> $name {
+                let d: $base = $name::reducer().reduce(x).into();
+                d.into()
             }
         }
 
@@ -410,15 +3976,20 @@ macro_rules! define_refined_modular_integer {
         impl Add for $name {
             type Output = $name;
             fn add(self, rhs: $name) -> $name {
+                if $name::TABLES {
+                    let r = self.table_op(rhs, $crate::tables::OpTables::add);
+                    $crate::shadow::check_modular(stringify!($name), "+", self, rhs, r);
+                    return r;
+                }
                 let a: $base = self.into();
                 let b: $base = rhs.into();
                 let a: BigUint = a.into();
                 let b: BigUint = b.into();
-                let c: BigUint = a + b;
-                let max: BigUint = $max.into();
-                let d: BigUint = c % max;
+                let d: BigUint = $name::reducer().reduce(a + b);
                 let d: $base = d.into();
-                d.into()
+                let r: $name = d.into();
+                $crate::shadow::check_modular(stringify!($name), "+", self, rhs, r);
+                r
             }
         }
 
@@ -426,15 +3997,21 @@ macro_rules! define_refined_modular_integer {
         impl Sub for $name {
             type Output = $name;
             fn sub(self, rhs: $name) -> $name {
+                if $name::TABLES {
+                    let r = self.table_op(rhs, $crate::tables::OpTables::sub);
+                    $crate::shadow::check_modular(stringify!($name), "-", self, rhs, r);
+                    return r;
+                }
                 let a: $base = self.into();
                 let b: $base = rhs.into();
                 let a: BigUint = a.into();
                 let b: BigUint = b.into();
-                let max: BigUint = $max.into();
-                let c: BigUint = if b > a { max.clone() - b + a } else { a - b };
-                let d: BigUint = c % max;
+                let max = $name::reducer().modulus();
+                let d: BigUint = if b > a { max - b + a } else { a - b };
                 let d: $base = d.into();
-                d.into()
+                let r: $name = d.into();
+                $crate::shadow::check_modular(stringify!($name), "-", self, rhs, r);
+                r
             }
         }
 
@@ -442,59 +4019,511 @@ macro_rules! define_refined_modular_integer {
         impl Mul for $name {
             type Output = $name;
             fn mul(self, rhs: $name) -> $name {
+                if $name::TABLES {
+                    let r = self.table_op(rhs, $crate::tables::OpTables::mul);
+                    $crate::shadow::check_modular(stringify!($name), "*", self, rhs, r);
+                    return r;
+                }
                 let a: $base = self.into();
                 let b: $base = rhs.into();
                 let a: BigUint = a.into();
                 let b: BigUint = b.into();
-                let c: BigUint = a * b;
-                let max: BigUint = $max.into();
-                let d: BigUint = c % max;
+                let d: BigUint = $name::reducer().reduce(a * b);
                 let d: $base = d.into();
-                d.into()
+                let r: $name = d.into();
+                $crate::shadow::check_modular(stringify!($name), "*", self, rhs, r);
+                r
             }
         }
 
-        /// **Warning**: panics on division by 0.
-        impl Div for $name {
-            type Output = $name;
-            fn div(self, rhs: $name) -> $name {
+        impl $name {
+            const FIELD_DIV: bool = $crate::__abstract_integer_options!(@has field_div; $($opt)*);
+
+            #[allow(dead_code)]
+            fn div_floor_vartime(self, rhs: $name) -> $name {
                 let a: $base = self.into();
                 let b: $base = rhs.into();
                 let a: BigUint = a.into();
                 let b: BigUint = b.into();
+                if b == BigUint::zero() {
+                    let d: $base = $crate::diagnostics::ArithmeticPanic::new(
+                        stringify!($name),
+                        "/",
+                        $crate::diagnostics::ArithmeticErrorKind::DivisionByZero,
+                        vec![a, b],
+                    )
+                    .secret($base::SECRET)
+                    .recover::<$name>()
+                    .into();
+                    return d.into();
+                }
                 let c: BigUint = a / b;
                 let max: BigUint = $max.into();
                 let d: BigUint = c % max;
                 let d: $base = d.into();
-                d.into()
+                let r: $name = d.into();
+                $crate::shadow::check_modular(stringify!($name), "/", self, rhs, r);
+                r
+            }
+
+            #[allow(dead_code)]
+            fn div_field_vartime(self, rhs: $name) -> $name {
+                if rhs == <$name as $crate::traits::ModularInteger>::zero() {
+                    return $crate::diagnostics::ArithmeticPanic::new(
+                        stringify!($name),
+                        "/",
+                        $crate::diagnostics::ArithmeticErrorKind::DivisionByZero,
+                        vec![self.into(), rhs.into()],
+                    )
+                    .secret($base::SECRET)
+                    .recover::<$name>()
+                    .into();
+                }
+                self * rhs.inv_vartime()
             }
         }
 
-        /// **Warning**: panics on division by 0.
-        impl Rem for $name {
-            type Output = $name;
-            fn rem(self, rhs: $name) -> $name {
+        impl $name {
+            #[allow(dead_code)]
+            fn rem_vartime(self, rhs: $name) -> $name {
                 let a: $base = self.into();
                 let b: $base = rhs.into();
                 let a: BigUint = a.into();
                 let b: BigUint = b.into();
+                if b == BigUint::zero() {
+                    let d: $base = $crate::diagnostics::ArithmeticPanic::new(
+                        stringify!($name),
+                        "%",
+                        $crate::diagnostics::ArithmeticErrorKind::DivisionByZero,
+                        vec![a, b],
+                    )
+                    .secret($base::SECRET)
+                    .recover::<$name>()
+                    .into();
+                    return d.into();
+                }
                 let c: BigUint = a % b;
                 let max: BigUint = $max.into();
                 let d: BigUint = c % max;
                 let d: $base = d.into();
-                d.into()
+                let r: $name = d.into();
+                $crate::shadow::check_modular(stringify!($name), "%", self, rhs, r);
+                r
+            }
+        }
+
+        $crate::__abstract_integer_options!(@unless secret; $($opt)*; {
+            impl $name {
+                /// Multiplicative inverse, for prime moduli. Variable-time, see `inv_ct`.
+                #[allow(dead_code)]
+                pub fn inv(self) -> Self {
+                    self.inv_vartime()
+                }
+
+                #[allow(dead_code)]
+                pub fn pow_felem(self, exp: Self) -> Self {
+                    self.pow_big_vartime(&exp.into())
+                }
+
+                /// Returns self to the power of the argument.
+                /// The exponent is a u128.
+                #[allow(dead_code)]
+                pub fn pow(self, exp: u128) -> Self {
+                    self.pow_big_vartime(&BigUint::from(exp))
+                }
+
+                /// Returns self to the power of a value of another type defined by this crate,
+                /// such as a dedicated scalar type, keeping exponents and elements apart.
+                #[allow(dead_code)]
+                pub fn pow_scalar<E: $crate::traits::Exponent>(self, exp: E) -> Self {
+                    self.pow_big_vartime(&exp.into())
+                }
+
+                /// Returns self to the power of the argument, negative exponents raising the
+                /// inverse of self, for prime moduli.
+                ///
+                /// **Warning**: panics on a negative power of 0.
+                #[allow(dead_code)]
+                pub fn pow_mod_signed(self, exp: i128) -> Self {
+                    self.pow_mod_signed_vartime(exp)
+                }
+
+                /// Returns self to the power of the argument, for exponents that do not fit in a
+                /// `u128` such as RSA exponents.
+                #[allow(dead_code)]
+                pub fn pow_mod_big(self, exp: &BigUint) -> Self {
+                    self.pow_big_vartime(exp)
+                }
+
+                /// Floor division of the representatives, reduced modulo the modulus.
+                ///
+                /// **Warning**: panics on division by 0.
+                #[allow(dead_code)]
+                pub fn div_floor(self, rhs: $name) -> $name {
+                    self.div_floor_vartime(rhs)
+                }
+
+                /// Field division: multiplication by the inverse of `rhs`, for prime moduli.
+                ///
+                /// **Warning**: panics on division by 0.
+                #[allow(dead_code)]
+                pub fn div_field(self, rhs: $name) -> $name {
+                    self.div_field_vartime(rhs)
+                }
+            }
+
+            $crate::__abstract_integer_options!(@unless no_div; $($opt)*; {
+                /// **Warning**: panics on division by 0.
+                impl Div for $name {
+                    type Output = $name;
+                    fn div(self, rhs: $name) -> $name {
+                        if $name::FIELD_DIV {
+                            self.div_field_vartime(rhs)
+                        } else {
+                            self.div_floor_vartime(rhs)
+                        }
+                    }
+                }
+
+                /// **Warning**: panics on division by 0.
+                impl Rem for $name {
+                    type Output = $name;
+                    fn rem(self, rhs: $name) -> $name {
+                        self.rem_vartime(rhs)
+                    }
+                }
+            });
+        });
+
+        $crate::__abstract_integer_options!(@if secret; $($opt)*; {
+            impl $name {
+                /// Returns the explicitly variable-time view of the value, see `vartime`.
+                #[allow(dead_code)]
+                pub fn var_time(self) -> $crate::vartime::VarTime<$name> {
+                    $crate::vartime::VarTime(self)
+                }
+            }
+
+            $crate::__abstract_integer_options!(@unless no_div; $($opt)*; {
+                /// **Warning**: panics on division by 0.
+                impl Div<$name> for $crate::vartime::VarTime<$name> {
+                    type Output = $name;
+                    fn div(self, rhs: $name) -> $name {
+                        if $name::FIELD_DIV {
+                            self.0.div_field_vartime(rhs)
+                        } else {
+                            self.0.div_floor_vartime(rhs)
+                        }
+                    }
+                }
+
+                /// **Warning**: panics on division by 0.
+                impl Rem<$name> for $crate::vartime::VarTime<$name> {
+                    type Output = $name;
+                    fn rem(self, rhs: $name) -> $name {
+                        self.0.rem_vartime(rhs)
+                    }
+                }
+            });
+
+            impl $crate::vartime::VarTimeModularOps<$name> for $crate::vartime::VarTime<$name> {
+                fn inv(self) -> $name {
+                    self.0.inv_vartime()
+                }
+
+                fn pow_felem(self, exp: $name) -> $name {
+                    self.0.pow_big_vartime(&exp.into())
+                }
+
+                fn pow(self, exp: u128) -> $name {
+                    self.0.pow_big_vartime(&BigUint::from(exp))
+                }
+
+                fn pow_scalar<E: $crate::traits::Exponent>(self, exp: E) -> $name {
+                    self.0.pow_big_vartime(&exp.into())
+                }
+
+                fn pow_mod_signed(self, exp: i128) -> $name {
+                    self.0.pow_mod_signed_vartime(exp)
+                }
+
+                fn pow_mod_big(self, exp: &BigUint) -> $name {
+                    self.0.pow_big_vartime(exp)
+                }
+
+                fn div_floor(self, rhs: $name) -> $name {
+                    self.0.div_floor_vartime(rhs)
+                }
+
+                fn div_field(self, rhs: $name) -> $name {
+                    self.0.div_field_vartime(rhs)
+                }
+            }
+        });
+    };
+}
+
+/// Defines the two fields of an elliptic curve from their moduli written in hexadecimal: the
+/// base field `{Curve}Base` of the coordinates and the scalar field `{Curve}Scalar` of the
+/// group order, both refined from a checked type `{Curve}Nat` sized for the larger modulus.
+///
+/// ```
+/// # use abstract_integers::*;
+/// # use num::{BigUint, CheckedSub, Zero};
+/// # use std::num::ParseIntError;
+/// # use std::ops::*;
+/// define_curve_fields!(
+///     P256,
+///     base = "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+///     scalar = "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+/// );
+/// let x = P256Base::from_literal(3);
+/// let k = P256Scalar::reduce_scalar_from_base(x);
+/// ```
+///
+/// There is deliberately no conversion between the two fields: mixing them up is a type error,
+/// and the reduction of a coordinate modulo the group order is explicit.
+#[macro_export]
+macro_rules! define_curve_fields {
+    ($curve:ident, base = $base:literal, scalar = $scalar:literal $(,)?) => {
+        $crate::paste::paste! {
+            $crate::define_abstract_integer_checked!(
+                [<$curve Nat>],
+                $crate::__max_bits($crate::__hex_bits($base), $crate::__hex_bits($scalar))
+            );
+
+            $crate::define_refined_modular_integer!(
+                [<$curve Base>],
+                [<$curve Nat>],
+                [<$curve Nat>]::from(
+                    BigUint::parse_bytes($base.trim_start_matches("0x").as_bytes(), 16)
+                        .expect("invalid hex modulus")
+                )
+            );
+
+            $crate::define_refined_modular_integer!(
+                [<$curve Scalar>],
+                [<$curve Nat>],
+                [<$curve Nat>]::from(
+                    BigUint::parse_bytes($scalar.trim_start_matches("0x").as_bytes(), 16)
+                        .expect("invalid hex modulus")
+                )
+            );
+
+            impl [<$curve Scalar>] {
+                /// Reduces an element of the base field modulo the order of the group.
+                #[allow(dead_code)]
+                pub fn reduce_scalar_from_base(x: [<$curve Base>]) -> Self {
+                    x.reduce_into()
+                }
+            }
+        }
+    };
+}
+
+/// Defines the multiplicative group of the units of a refined type, its elements coprime to the
+/// modulus:
+///
+/// ```
+/// # use abstract_integers::*;
+/// # use num::{BigUint, CheckedSub, Zero};
+/// # use std::num::ParseIntError;
+/// # use std::ops::*;
+/// # define_abstract_integer_checked!(Nat, 64);
+/// # let e = 3;
+/// define_refined_modular_integer!(ZN, Nat, Nat::from_literal(3 * 5 * 11));
+/// define_unit_group!(ZNStar, ZN);
+/// let u = ZNStar::new(ZN::from_literal(7)).expect("7 is coprime to 165");
+/// let v = u.pow(e) * u.inv();
+/// let x: ZN = v.into();
+/// # assert_eq!(x, ZN::from_literal(49));
+/// ```
+///
+/// Elements can only be built from values coprime to the modulus, and the group only has the
+/// multiplication, exponentiation and inversion, which do not require a prime modulus. Elements
+/// convert back to the refined type.
+#[macro_export]
+macro_rules! define_unit_group {
+    ($(#[$attr:meta])* $name:ident, $ring:ident) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        pub struct $name($ring);
+
+        impl $name {
+            /// Returns `x` as a unit, or `None` if it is not coprime to the modulus.
+            #[allow(dead_code)]
+            pub fn new(x: $ring) -> Option<Self> {
+                $crate::number_theory::mod_inverse(&x.into(), $ring::reducer().modulus())
+                    .map(|_| $name(x))
+            }
+
+            /// The identity of the group.
+            #[allow(dead_code)]
+            pub fn one() -> Self {
+                $name(<$ring as $crate::traits::ModularInteger>::one())
+            }
+
+            /// Returns the element of the refined type.
+            #[allow(dead_code)]
+            pub fn value(self) -> $ring {
+                self.0
+            }
+
+            /// Returns the inverse of the unit, computed with the extended Euclidean algorithm.
+            #[allow(dead_code)]
+            pub fn inv(self) -> Self {
+                let inverse =
+                    $crate::number_theory::mod_inverse(&self.0.into(), $ring::reducer().modulus())
+                        .expect("units are invertible");
+                $name(inverse.into())
+            }
+
+            /// Returns self to the power of the argument.
+            #[allow(dead_code)]
+            pub fn pow(self, exp: u128) -> Self {
+                $name(<$ring as $crate::traits::ModularInteger>::pow(self.0, exp))
+            }
+        }
+
+        impl std::convert::TryFrom<$ring> for $name {
+            type Error = $crate::diagnostics::NotAUnitError;
+
+            fn try_from(x: $ring) -> Result<$name, Self::Error> {
+                $name::new(x).ok_or_else(|| {
+                    $crate::diagnostics::NotAUnitError::new(stringify!($name), x.into())
+                        .secret($ring::SECRET)
+                })
+            }
+        }
+
+        impl From<$name> for $ring {
+            fn from(x: $name) -> $ring {
+                x.0
+            }
+        }
+
+        impl Mul for $name {
+            type Output = $name;
+            fn mul(self, rhs: $name) -> $name {
+                $name(self.0 * rhs.0)
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                std::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                std::fmt::Debug::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+/// Builds a value of a type defined by this crate from an integer literal, checking at compile
+/// time that the literal fits in the bit width of the type. Literals can use `_` separators
+/// and be written in hexadecimal, octal or binary; decimal literals must fit in a `u128`.
+///
+/// ```
+/// # use abstract_integers::*;
+/// # use num::{BigUint, CheckedSub, Zero};
+/// # use std::num::ParseIntError;
+/// # use std::ops::*;
+/// # define_abstract_integer_checked!(BigBounded, 256);
+/// # define_refined_modular_integer!(
+/// #     Felem,
+/// #     BigBounded,
+/// #     BigBounded::pow2(255) - BigBounded::from_literal(19)
+/// # );
+/// let x = nat!(Felem, 0x7fff_ffff_ffff_ffff);
+/// ```
+///
+/// Literals of refined types are also checked against the modulus when it is written as
+/// `Base::from_literal(m)`. Other moduli are not constants, so a literal that fits in the width
+/// of the base but is not below the modulus is only rejected at run time, by a panic.
+#[macro_export]
+macro_rules! nat {
+    ($t:ty, $lit:literal) => {{
+        const _: () = assert!(
+            $crate::__literal_bits(stringify!($lit)) <= <$t>::BITS
+                && $crate::__literal_below(stringify!($lit), <$t>::__LITERAL_BOUND),
+            concat!("literal ", stringify!($lit), " too big for type ", stringify!($t))
+        );
+        <$t>::__from_nat_literal(stringify!($lit))
+    }};
+}
+
+/// Emits `extern "C"` functions to exchange values of a type defined with this crate with C
+/// code.
+///
+/// The generated types are `repr(transparent)` wrappers around their canonical byte
+/// representation, so on the C side a value is simply an array of `sizeof` bytes, e.g.
+/// `uint8_t[32]` for a 256-bit integer. The macro takes the type and the names of the
+/// constructor and accessor to emit:
+///
+/// ```
+/// # use abstract_integers::*;
+/// # use num::{BigUint, CheckedSub, Zero};
+/// # use std::num::ParseIntError;
+/// # use std::ops::*;
+/// # define_abstract_integer_checked!(BigBounded, 256);
+/// # define_refined_modular_integer!(
+/// #     Felem,
+/// #     BigBounded,
+/// #     BigBounded::pow2(255) - BigBounded::from_literal(19)
+/// # );
+/// define_abstract_integer_ffi!(Felem, felem_from_bytes, felem_to_bytes);
+/// ```
+///
+/// The constructor `int32_t felem_from_bytes(const uint8_t *bytes, size_t len, Felem *out)`
+/// reads `len` bytes in the canonical byte order of the type and returns `0`, or `-1` if the
+/// value does not fit in the type. The accessor `void felem_to_bytes(const Felem *x, uint8_t
+/// *out)` copies the canonical representation of `x` to `out`. Neither function panics.
+#[macro_export]
+macro_rules! define_abstract_integer_ffi {
+    ($name:ident, $from_bytes:ident, $to_bytes:ident) => {
+        /// Reads `len` bytes at `bytes` into `*out`, returns `0` on success and `-1` if the
+        /// value does not fit in the type.
+        ///
+        /// # Safety
+        ///
+        /// `bytes` must be valid for reads of `len` bytes and `out` valid for writes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $from_bytes(bytes: *const u8, len: usize, out: *mut $name) -> i32 {
+            let v = std::slice::from_raw_parts(bytes, len);
+            match $name::try_from_bytes(v) {
+                Some(x) => {
+                    *out = x;
+                    0
+                }
+                None => -1,
             }
         }
+
+        /// Copies the canonical representation of `*x` to `out`.
+        ///
+        /// # Safety
+        ///
+        /// `x` must be valid for reads and `out` valid for writes of `size_of::<Name>()` bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $to_bytes(x: *const $name, out: *mut u8) {
+            std::ptr::copy_nonoverlapping(x as *const u8, out, std::mem::size_of::<$name>());
+        }
     };
 }
 
 // Natural integer bounded by std::usize::MAX
 define_abstract_integer_checked!(SizeNatExample, 64);
 
+// Field over the 9th Mersenne prime, reduced with `reduction::ReductionKind::Mersenne`
 define_refined_modular_integer!(
     SizeNatFieldExample,
     SizeNatExample,
     SizeNatExample::pow2(61) - SizeNatExample::from_literal(1)
 );
 
+#[allow(clippy::useless_conversion, clippy::unused_unit)]
 mod tests;
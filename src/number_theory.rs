@@ -0,0 +1,414 @@
+//! Number-theoretic algorithms on `BigUint`, used by the methods generated by the macros of
+//! this crate.
+
+use num::{BigUint, Integer, One, ToPrimitive, Zero};
+use std::collections::HashMap;
+
+/// Computes the multiplicative order of `x` modulo `modulus`, given the factorization of the
+/// order of the multiplicative group as a list of `(prime, exponent)` pairs.
+///
+/// **Warning**: panics if `x` is not invertible, i.e. if its order does not divide the group
+/// order described by `factors`.
+pub fn multiplicative_order(x: &BigUint, modulus: &BigUint, factors: &[(BigUint, u32)]) -> BigUint {
+    let group_order = factors
+        .iter()
+        .fold(BigUint::one(), |acc, (q, e)| acc * num::pow(q.clone(), *e as usize));
+    assert!(
+        x.modpow(&group_order, modulus).is_one(),
+        "{} is not an element of the multiplicative group of order {} modulo {}",
+        x,
+        group_order,
+        modulus
+    );
+    let mut order = group_order;
+    for (q, e) in factors {
+        order /= num::pow(q.clone(), *e as usize);
+        let mut y = x.modpow(&order, modulus);
+        while !y.is_one() {
+            y = y.modpow(q, modulus);
+            order *= q;
+        }
+    }
+    order
+}
+
+/// Returns `true` if `x` generates the whole multiplicative group modulo `modulus`, whose order
+/// is given by its factorization as a list of `(prime, exponent)` pairs.
+pub fn is_generator(x: &BigUint, modulus: &BigUint, factors: &[(BigUint, u32)]) -> bool {
+    let group_order = factors
+        .iter()
+        .fold(BigUint::one(), |acc, (q, e)| acc * num::pow(q.clone(), *e as usize));
+    if (x % modulus).is_zero() || !x.modpow(&group_order, modulus).is_one() {
+        return false;
+    }
+    factors
+        .iter()
+        .all(|(q, _)| !x.modpow(&(&group_order / q), modulus).is_one())
+}
+
+/// Returns the smallest primitive root modulo the prime `p`, given the factorization of `p - 1`
+/// as a list of `(prime, exponent)` pairs.
+pub fn primitive_root(p: &BigUint, factors: &[(BigUint, u32)]) -> BigUint {
+    if p == &BigUint::from(2u32) {
+        return BigUint::one();
+    }
+    let mut g = BigUint::from(2u32);
+    while !is_generator(&g, p, factors) {
+        g += 1u32;
+        assert!(&g < p, "{} has no primitive root, is it prime?", p);
+    }
+    g
+}
+
+/// Returns a primitive `n`-th root of unity modulo the prime `p`, given the factorization of
+/// `p - 1` as a list of `(prime, exponent)` pairs.
+///
+/// **Warning**: panics if `n` does not divide `p - 1`.
+pub fn root_of_unity(n: &BigUint, p: &BigUint, factors: &[(BigUint, u32)]) -> BigUint {
+    let group_order = p - BigUint::one();
+    assert!(
+        !n.is_zero() && (&group_order % n).is_zero(),
+        "there is no primitive {}-th root of unity modulo {}",
+        n,
+        p
+    );
+    primitive_root(p, factors).modpow(&(group_order / n), p)
+}
+
+/// Returns `(m, k)` with `k >= 2` as big as possible such that `n = m^k` and `m >= 2`, or
+/// `None` if `n` is not a perfect power.
+pub fn perfect_power(n: &BigUint) -> Option<(BigUint, u32)> {
+    let two = BigUint::from(2u32);
+    if n < &(&two * &two) {
+        return None;
+    }
+    (2..=n.bits() as u32).rev().find_map(|k| {
+        let m = n.nth_root(k);
+        if m >= two && &num::pow(m.clone(), k as usize) == n {
+            Some((m, k))
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns the Jacobi symbol `(a / n)`: 0 if `a` and `n` are not coprime, and otherwise 1 or
+/// -1, the Legendre symbol when `n` is prime.
+///
+/// **Warning**: panics if `n` is even.
+pub fn jacobi(a: &BigUint, n: &BigUint) -> i8 {
+    let low_bits = |x: &BigUint| (x % 8u32).to_u32().unwrap();
+    assert!(low_bits(n) % 2 == 1, "Jacobi symbol modulo the even number {}", n);
+    let (mut a, mut n) = (a % n, n.clone());
+    let mut result = 1;
+    while !a.is_zero() {
+        while low_bits(&a) % 2 == 0 {
+            a >>= 1;
+            if low_bits(&n) == 3 || low_bits(&n) == 5 {
+                result = -result;
+            }
+        }
+        if low_bits(&a) % 4 == 3 && low_bits(&n) % 4 == 3 {
+            result = -result;
+        }
+        std::mem::swap(&mut a, &mut n);
+        a %= &n;
+    }
+    if n.is_one() {
+        result
+    } else {
+        0
+    }
+}
+
+const SMALL_PRIMES: [u32; 25] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+];
+
+/// Returns `Some(true)` if `n` is one of the primes below 100, `Some(false)` if it is below 2
+/// or has one of them as a factor, and `None` otherwise.
+fn trial_division_small(n: &BigUint) -> Option<bool> {
+    if n < &BigUint::from(2u32) {
+        return Some(false);
+    }
+    for p in SMALL_PRIMES.iter() {
+        if n == &BigUint::from(*p) {
+            return Some(true);
+        }
+        if (n % p).is_zero() {
+            return Some(false);
+        }
+    }
+    None
+}
+
+/// Strong probable-prime test of the odd `n > 2` in base `a`.
+fn strong_probable_prime(n: &BigUint, a: &BigUint) -> bool {
+    let n_minus_one = n - 1u32;
+    let mut s = 0;
+    let mut d = n_minus_one.clone();
+    while (&d % 2u32).is_zero() {
+        d >>= 1;
+        s += 1;
+    }
+    let mut x = a.modpow(&d, n);
+    if x.is_one() || x == n_minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = &x * &x % n;
+        if x == n_minus_one {
+            return true;
+        }
+    }
+    false
+}
+
+/// Deterministic Miller-Rabin test with the first `rounds` primes as bases. It never rejects a
+/// prime, and with 13 rounds or more it is exact below `3.3 * 10^24`. Since the bases are fixed,
+/// composites passing it can be built on purpose: use `is_probable_prime` or `is_prime_bpsw`
+/// on untrusted inputs.
+///
+/// **Warning**: panics if `rounds` is above 25.
+pub fn is_probable_prime_fixed_bases(n: &BigUint, rounds: usize) -> bool {
+    assert!(
+        rounds <= SMALL_PRIMES.len(),
+        "at most {} fixed bases are available, {} rounds requested",
+        SMALL_PRIMES.len(),
+        rounds
+    );
+    if let Some(result) = trial_division_small(n) {
+        return result;
+    }
+    SMALL_PRIMES
+        .iter()
+        .take(rounds)
+        .all(|a| strong_probable_prime(n, &BigUint::from(*a)))
+}
+
+/// Miller-Rabin test with `rounds` bases drawn uniformly from `[2, n - 2]`. It never rejects a
+/// prime, and accepts a composite with probability at most `4^-rounds`.
+#[cfg(feature = "rand")]
+pub fn is_probable_prime<R>(n: &BigUint, rounds: usize, rng: &mut R) -> bool
+where
+    R: rand::RngCore + ?Sized,
+{
+    if let Some(result) = trial_division_small(n) {
+        return result;
+    }
+    let range = n - 3u32;
+    let mut bytes = vec![0u8; n.bits().div_ceil(8) + 16];
+    (0..rounds).all(|_| {
+        rng.fill_bytes(&mut bytes);
+        let a = BigUint::from_bytes_be(&bytes) % &range + 2u32;
+        strong_probable_prime(n, &a)
+    })
+}
+
+/// Strong Lucas probable-prime test of the odd `n`, not a perfect square, with the parameters
+/// of Selfridge's method A.
+fn strong_lucas_probable_prime(n: &BigUint) -> bool {
+    // Signed values are handled through their residues modulo n.
+    let residue = |x: i64| {
+        let abs = BigUint::from(x.unsigned_abs()) % n;
+        if x < 0 && !abs.is_zero() {
+            n - abs
+        } else {
+            abs
+        }
+    };
+    let mut d: i64 = 5;
+    loop {
+        match jacobi(&residue(d), n) {
+            -1 => break,
+            0 if BigUint::from(d.unsigned_abs()) != *n => return false,
+            _ => {}
+        }
+        d = if d > 0 { -(d + 2) } else { -d + 2 };
+    }
+    let (big_d, q) = (residue(d), residue((1 - d) / 4));
+    let half = |x: BigUint| if (&x % 2u32).is_zero() { x / 2u32 } else { (x + n) / 2u32 };
+    let two = BigUint::from(2u32);
+    let double_v = |v: &BigUint, qk: &BigUint| (v * v + n * &two - (&two * qk) % n) % n;
+    let mut k = n + 1u32;
+    let mut s = 0;
+    while (&k % 2u32).is_zero() {
+        k >>= 1;
+        s += 1;
+    }
+    let (mut u, mut v, mut qk) = (BigUint::one(), BigUint::one(), q.clone());
+    for i in (0..k.bits() - 1).rev() {
+        u = &u * &v % n;
+        v = double_v(&v, &qk);
+        qk = &qk * &qk % n;
+        if ((&k >> i) % 2u32).is_one() {
+            let (u1, v1) = (&u + &v, &big_d * &u + &v);
+            u = half(u1 % n);
+            v = half(v1 % n);
+            qk = &qk * &q % n;
+        }
+    }
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        v = double_v(&v, &qk);
+        qk = &qk * &qk % n;
+        if v.is_zero() {
+            return true;
+        }
+    }
+    false
+}
+
+/// Baillie-PSW test: a strong probable-prime test in base 2 followed by a strong Lucas test.
+/// It is deterministic and no composite passing it is known.
+pub fn is_prime_bpsw(n: &BigUint) -> bool {
+    if let Some(result) = trial_division_small(n) {
+        return result;
+    }
+    if !strong_probable_prime(n, &BigUint::from(2u32)) {
+        return false;
+    }
+    let root = n.nth_root(2);
+    if &root * &root == *n {
+        return false;
+    }
+    strong_lucas_probable_prime(n)
+}
+
+/// Returns a random prime of exactly `bits` bits, drawing odd candidates with the top bit set
+/// until one passes `is_prime_bpsw`.
+///
+/// **Warning**: panics if `bits` is below 2.
+#[cfg(feature = "rand")]
+pub fn random_prime<R: rand::RngCore + ?Sized>(bits: usize, rng: &mut R) -> BigUint {
+    assert!(bits >= 2, "there is no prime of {} bits", bits);
+    let mut bytes = vec![0u8; bits.div_ceil(8)];
+    loop {
+        rng.fill_bytes(&mut bytes);
+        let mut candidate = BigUint::from_bytes_be(&bytes) >> (8 * bytes.len() - bits);
+        candidate |= (BigUint::one() << (bits - 1)) | BigUint::one();
+        if is_prime_bpsw(&candidate) {
+            return candidate;
+        }
+    }
+}
+
+/// Returns a non-trivial divisor of the odd composite `n`, which is not a prime power of a
+/// small prime, with Pollard's rho algorithm.
+fn pollard_rho(n: &BigUint) -> BigUint {
+    let mut c = BigUint::one();
+    loop {
+        let f = |x: &BigUint| (x * x + &c) % n;
+        let (mut x, mut y) = (BigUint::from(2u32), BigUint::from(2u32));
+        let mut d = BigUint::one();
+        while d.is_one() {
+            x = f(&x);
+            y = f(&f(&y));
+            let diff = if x > y { &x - &y } else { &y - &x };
+            d = diff.gcd(n);
+        }
+        if &d != n {
+            return d;
+        }
+        c += 1u32;
+    }
+}
+
+/// Factors `n` by trial division by the small primes and Pollard's rho algorithm, returning
+/// `(prime, exponent)` pairs in increasing order. The primality of the factors is checked with
+/// `is_prime_bpsw`.
+///
+/// **Warning**: best effort, meant for tests and tooling on moderate sizes: the running time
+/// grows with the square root of the second largest prime factor. Panics if `n` is 0.
+pub fn factor(n: &BigUint) -> Vec<(BigUint, u32)> {
+    assert!(!n.is_zero(), "cannot factor zero");
+    let mut primes = Vec::new();
+    let mut n = n.clone();
+    for p in SMALL_PRIMES.iter() {
+        while (&n % p).is_zero() {
+            n /= *p;
+            primes.push(BigUint::from(*p));
+        }
+    }
+    let mut composites = if n.is_one() { vec![] } else { vec![n] };
+    while let Some(m) = composites.pop() {
+        if is_prime_bpsw(&m) {
+            primes.push(m);
+        } else {
+            let d = pollard_rho(&m);
+            composites.push(&m / &d);
+            composites.push(d);
+        }
+    }
+    primes.sort();
+    let mut factors: Vec<(BigUint, u32)> = Vec::new();
+    for p in primes {
+        match factors.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+    factors
+}
+
+/// Returns the smallest `x` below `bound` such that `base^x = target` modulo `modulus`, or
+/// `None` if there is none, with the baby-step giant-step algorithm. The work and the memory
+/// are proportional to the square root of `bound`, which must stay small.
+pub fn discrete_log(
+    base: &BigUint,
+    target: &BigUint,
+    modulus: &BigUint,
+    bound: u64,
+) -> Option<u64> {
+    let (base, target) = (base % modulus, target % modulus);
+    // m * m > bound, so that every exponent below bound is i * m - j for some i <= m and j < m.
+    let m = (BigUint::from(bound).sqrt() + 1u32).to_u64().expect("the square root fits in 33 bits");
+    // Baby steps: the exponents below m are tried directly, and target * base^j is stored for
+    // each j below m.
+    let mut baby: HashMap<BigUint, Vec<u64>> = HashMap::new();
+    let (mut power, mut y) = (BigUint::one() % modulus, target.clone());
+    for j in 0..m.min(bound) {
+        if power == target {
+            return Some(j);
+        }
+        baby.entry(y.clone()).or_default().push(j);
+        power = power * &base % modulus;
+        y = y * &base % modulus;
+    }
+    // Giant steps: base^(i * m) = target * base^j for x = i * m - j. The converse does not hold
+    // when base is not invertible, so the candidates are checked, the smallest first.
+    let giant = base.modpow(&BigUint::from(m), modulus);
+    let mut z = BigUint::one() % modulus;
+    for i in 1..=m {
+        z = z * &giant % modulus;
+        for j in baby.get(&z).into_iter().flatten().rev() {
+            let x = i * m - j;
+            if x < bound && base.modpow(&BigUint::from(x), modulus) == target {
+                return Some(x);
+            }
+        }
+    }
+    None
+}
+
+/// Returns the inverse of `x` modulo `modulus` with the extended Euclidean algorithm, or `None`
+/// if `x` is not coprime to the modulus. Unlike the inversion of the refined types, the modulus
+/// does not have to be prime.
+pub fn mod_inverse(x: &BigUint, modulus: &BigUint) -> Option<BigUint> {
+    // Invariant: r_i = t_i * x modulo the modulus, t_i being kept reduced.
+    let (mut r0, mut r1) = (modulus.clone(), x % modulus);
+    let (mut t0, mut t1) = (BigUint::zero(), BigUint::one() % modulus);
+    while !r1.is_zero() {
+        let (q, r2) = r0.div_rem(&r1);
+        let t2 = (&t0 + modulus - (&q * &t1) % modulus) % modulus;
+        r0 = std::mem::replace(&mut r1, r2);
+        t0 = std::mem::replace(&mut t1, t2);
+    }
+    if r0.is_one() {
+        Some(t0)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,13 @@
+//! Polynomial helpers generic over the types implementing `ModularInteger`.
+
+use crate::traits::ModularInteger;
+
+/// Evaluates the polynomial `coeffs[0] + coeffs[1] * x + coeffs[2] * x^2 + ...` at `x` with
+/// Horner's rule. The coefficients are given from the lowest degree to the highest, and the empty
+/// polynomial evaluates to zero.
+pub fn horner<T: ModularInteger>(coeffs: &[T], x: T) -> T {
+    coeffs
+        .iter()
+        .rev()
+        .fold(T::zero(), |acc, coeff| acc * x + *coeff)
+}
@@ -0,0 +1,140 @@
+//! Overflow-promoting arithmetic for prototyping the bounds of a specification.
+//!
+//! `Promoting<T>` wraps a value of a type implementing `CheckedInteger`. When an operation
+//! overflows the bound of `T`, the result is promoted to an unbounded `BigUint` instead of
+//! panicking, and the computation continues exactly. Each promotion is recorded as an
+//! `ArithmeticPanic` in a thread-local log, that harnesses read with `take_promotions` to report
+//! where the bounds were too tight. Values stay unbounded once promoted.
+
+use crate::diagnostics::{ArithmeticErrorKind, ArithmeticPanic};
+use crate::traits::CheckedInteger;
+use num::{BigUint, CheckedSub};
+use std::cell::RefCell;
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+thread_local! {
+    static PROMOTIONS: RefCell<Vec<ArithmeticPanic>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Returns and clears the promotions recorded on the current thread, in order.
+pub fn take_promotions() -> Vec<ArithmeticPanic> {
+    PROMOTIONS.with(|p| std::mem::take(&mut *p.borrow_mut()))
+}
+
+/// A value of `T`, or the exact result of an operation that overflowed `T`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Promoting<T> {
+    /// No operation leading to the value overflowed.
+    Bounded(T),
+    /// The exact value, after an overflow of `T`.
+    Unbounded(BigUint),
+}
+
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+impl<T> Promoting<T>
+where
+    T: CheckedInteger + From<BigUint> + Into<BigUint>,
+{
+    /// Returns the exact value.
+    pub fn value(&self) -> BigUint {
+        match self {
+            Promoting::Bounded(x) => (*x).into(),
+            Promoting::Unbounded(x) => x.clone(),
+        }
+    }
+
+    /// Returns `true` if no operation leading to this value overflowed.
+    pub fn is_bounded(&self) -> bool {
+        matches!(self, Promoting::Bounded(_))
+    }
+
+    /// Returns the value if it is still bounded.
+    pub fn bounded(self) -> Option<T> {
+        match self {
+            Promoting::Bounded(x) => Some(x),
+            Promoting::Unbounded(_) => None,
+        }
+    }
+
+    fn combine(self, rhs: Self, operation: &'static str, c: BigUint) -> Self {
+        if !(self.is_bounded() && rhs.is_bounded()) {
+            return Promoting::Unbounded(c);
+        }
+        if c <= T::max_value() {
+            return Promoting::Bounded(c.into());
+        }
+        let error = ArithmeticPanic::new(
+            short_type_name::<T>(),
+            operation,
+            ArithmeticErrorKind::Overflow,
+            vec![self.value(), rhs.value()],
+        )
+        .secret(T::SECRET);
+        PROMOTIONS.with(|p| p.borrow_mut().push(error));
+        Promoting::Unbounded(c)
+    }
+}
+
+impl<T> From<T> for Promoting<T> {
+    fn from(x: T) -> Self {
+        Promoting::Bounded(x)
+    }
+}
+
+impl<T> Add for Promoting<T>
+where
+    T: CheckedInteger + From<BigUint> + Into<BigUint>,
+{
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let c = self.value() + rhs.value();
+        self.combine(rhs, "+", c)
+    }
+}
+
+/// **Warning**: panics on underflow, promotion only handles overflows.
+impl<T> Sub for Promoting<T>
+where
+    T: CheckedInteger + From<BigUint> + Into<BigUint> + 'static,
+{
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        let (a, b) = (self.value(), rhs.value());
+        let c = a.checked_sub(&b).unwrap_or_else(|| {
+            ArithmeticPanic::new(
+                short_type_name::<T>(),
+                "-",
+                ArithmeticErrorKind::Underflow,
+                vec![a.clone(), b.clone()],
+            )
+            .secret(T::SECRET)
+            .recover::<T>()
+        });
+        self.combine(rhs, "-", c)
+    }
+}
+
+impl<T> Mul for Promoting<T>
+where
+    T: CheckedInteger + From<BigUint> + Into<BigUint>,
+{
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let c = self.value() * rhs.value();
+        self.combine(rhs, "*", c)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Promoting<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Promoting::Bounded(x) => write!(f, "{}", x),
+            Promoting::Unbounded(x) => write!(f, "{} (unbounded)", x),
+        }
+    }
+}
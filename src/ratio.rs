@@ -0,0 +1,139 @@
+//! Exact non-negative fractions of the types implementing `AbstractInteger`.
+//!
+//! The operations are computed on `BigUint` and reduced by the gcd before being converted back,
+//! so only the reduced numerator and denominator have to fit in the type.
+
+use crate::traits::AbstractInteger;
+use num::{BigUint, CheckedSub, Integer, Zero};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// A fraction `numer / denom` in lowest terms, with a non-zero denominator.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Ratio<T> {
+    numer: T,
+    denom: T,
+}
+
+impl<T: AbstractInteger> Ratio<T> {
+    fn reduced(numer: BigUint, denom: BigUint) -> Self {
+        assert!(!denom.is_zero(), "fraction with a zero denominator");
+        let g = numer.gcd(&denom);
+        let g = if g.is_zero() { BigUint::from(1u32) } else { g };
+        Ratio {
+            numer: (numer / &g).into(),
+            denom: (denom / g).into(),
+        }
+    }
+
+    /// Returns `numer / denom` in lowest terms.
+    ///
+    /// **Warning**: panics if `denom` is 0.
+    pub fn new(numer: T, denom: T) -> Self {
+        Self::reduced(numer.into(), denom.into())
+    }
+
+    /// Returns `x / 1`.
+    pub fn from_integer(x: T) -> Self {
+        Ratio {
+            numer: x,
+            denom: T::from_literal(1),
+        }
+    }
+
+    /// The numerator, in lowest terms.
+    pub fn numer(&self) -> &T {
+        &self.numer
+    }
+
+    /// The denominator, in lowest terms.
+    pub fn denom(&self) -> &T {
+        &self.denom
+    }
+
+    fn parts(&self) -> (BigUint, BigUint) {
+        (self.numer.clone().into(), self.denom.clone().into())
+    }
+
+    /// Returns the largest integer below the fraction.
+    pub fn floor(&self) -> T {
+        let (n, d) = self.parts();
+        (n / d).into()
+    }
+
+    /// Returns the smallest integer above the fraction.
+    pub fn ceil(&self) -> T {
+        let (n, d) = self.parts();
+        n.div_ceil(&d).into()
+    }
+
+    /// Returns `denom / numer`.
+    ///
+    /// **Warning**: panics if the fraction is 0.
+    pub fn recip(&self) -> Self {
+        Self::new(self.denom.clone(), self.numer.clone())
+    }
+}
+
+impl<T: AbstractInteger> Add for Ratio<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let ((a, b), (c, d)) = (self.parts(), rhs.parts());
+        Self::reduced(a * &d + c * &b, b * d)
+    }
+}
+
+/// **Warning**: panics if the result is negative.
+impl<T: AbstractInteger> Sub for Ratio<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        let ((a, b), (c, d)) = (self.parts(), rhs.parts());
+        let n = (a * &d)
+            .checked_sub(&(c * &b))
+            .expect("negative difference of fractions");
+        Self::reduced(n, b * d)
+    }
+}
+
+impl<T: AbstractInteger> Mul for Ratio<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let ((a, b), (c, d)) = (self.parts(), rhs.parts());
+        Self::reduced(a * c, b * d)
+    }
+}
+
+/// **Warning**: panics on division by 0.
+impl<T: AbstractInteger> Div for Ratio<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let ((a, b), (c, d)) = (self.parts(), rhs.parts());
+        Self::reduced(a * d, b * c)
+    }
+}
+
+impl<T: AbstractInteger> PartialOrd for Ratio<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: AbstractInteger> Ord for Ratio<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let ((a, b), (c, d)) = (self.parts(), other.parts());
+        (a * d).cmp(&(c * b))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Ratio<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.numer, self.denom)
+    }
+}
+
+impl<T: fmt::Display> fmt::Debug for Ratio<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.numer, self.denom)
+    }
+}
@@ -0,0 +1,101 @@
+//! Signed-digit recodings of scalars, for the specifications of elliptic-curve scalar
+//! multiplications.
+//!
+//! The width-`w` non-adjacent form (wNAF) of `k` is the sequence of digits `d_i`, least
+//! significant first, such that `k = sum(d_i 2^i)`, every non-zero digit is odd and below
+//! `2^(w-1)` in absolute value, and any `w` consecutive digits contain at most one non-zero
+//! digit. The NAF is the wNAF of width 2, with digits in `{-1, 0, 1}`.
+//!
+//! The wNAF has a length and a pattern of zero digits that depend on the scalar, so ladder-style
+//! specifications use instead the signed fixed-window recoding of `signed_digits_ct`: digits in
+//! base `2^w` in `[-2^(w-1), 2^(w-1))`, in a number depending only on the bits of the type, and
+//! computed without branching on the scalar.
+
+use num::{BigUint, ToPrimitive, Zero};
+
+/// Returns the width-`w` NAF of `k`, least significant digit first, with no trailing zero
+/// digits. Zero has no digits.
+///
+/// **Warning**: panics if `w` is not between 2 and 8.
+pub fn wnaf(k: &BigUint, w: usize) -> Vec<i8> {
+    assert!((2..=8).contains(&w), "invalid wNAF width {}", w);
+    let modulus = 1i64 << w;
+    let mut k = k.clone();
+    let mut digits = Vec::new();
+    while !k.is_zero() {
+        let mut digit = 0;
+        if !(&k % 2u32).is_zero() {
+            digit = (&k % modulus as u64).to_i64().unwrap();
+            if digit >= modulus / 2 {
+                digit -= modulus;
+            }
+            if digit > 0 {
+                k -= digit as u64;
+            } else {
+                k += (-digit) as u64;
+            }
+        }
+        digits.push(digit as i8);
+        k >>= 1;
+    }
+    digits
+}
+
+/// Number of digits of the signed fixed-window recoding of width `w` of `bits`-bit scalars.
+///
+/// There is one more window than needed for the bits, with room for the final carry.
+pub const fn signed_digits_len(bits: usize, w: usize) -> usize {
+    (bits + w + 1) / w
+}
+
+/// Returns the signed fixed-window recoding of width `w` of the `bits`-bit scalar given as 64-bit
+/// limbs, least significant first: `signed_digits_len(bits, w)` digits `d_i` in
+/// `[-2^(w-1), 2^(w-1))` such that the scalar is `sum(d_i 2^(w i))`.
+///
+/// The memory accesses and operations only depend on `bits` and `w`, not on the value.
+///
+/// **Warning**: panics if `w` is not between 2 and 8, or if the limbs do not hold `bits` bits.
+pub fn signed_digits_ct(limbs: &[u64], bits: usize, w: usize) -> Vec<i8> {
+    assert!((2..=8).contains(&w), "invalid window width {}", w);
+    assert!(limbs.len() * 64 >= bits, "{} limbs do not hold {} bits", limbs.len(), bits);
+    let window = |i: usize| -> i64 {
+        (0..w)
+            .map(|j| i * w + j)
+            .filter(|pos| *pos < bits)
+            .map(|pos| (((limbs[pos / 64] >> (pos % 64)) & 1) as i64) << (pos - i * w))
+            .sum()
+    };
+    let half = 1i64 << (w - 1);
+    let mut carry = 0i64;
+    (0..signed_digits_len(bits, w))
+        .map(|i| {
+            let x = window(i) + carry;
+            carry = (x + half) >> w;
+            (x - (carry << w)) as i8
+        })
+        .collect()
+}
+
+/// Returns the value of signed digits in base `2^w`, least significant first.
+///
+/// **Warning**: panics if the value is negative.
+pub fn from_signed_window_digits(digits: &[i8], w: usize) -> BigUint {
+    let (mut pos, mut neg) = (BigUint::zero(), BigUint::zero());
+    for (i, d) in digits.iter().enumerate() {
+        let term = BigUint::from(d.unsigned_abs()) << (w * i);
+        if *d > 0 {
+            pos += term;
+        } else {
+            neg += term;
+        }
+    }
+    assert!(pos >= neg, "negative signed-digit representation");
+    pos - neg
+}
+
+/// Returns the value of signed digits in base 2, least significant first.
+///
+/// **Warning**: panics if the value is negative.
+pub fn from_signed_digits(digits: &[i8]) -> BigUint {
+    from_signed_window_digits(digits, 1)
+}
@@ -0,0 +1,101 @@
+//! Reduction modulo the modulus of a refined type, with fast paths for moduli of special
+//! shapes. Each refined type builds its `Reducer` the first time it reduces a value, detecting
+//! the shape of the modulus, and reuses it afterwards.
+
+use num::{BigUint, ToPrimitive};
+
+/// Shape of a modulus, selecting the reduction algorithm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReductionKind {
+    /// Euclidean division.
+    Generic,
+    /// `2^bits`: reduction by masking.
+    PowerOfTwo { bits: usize },
+    /// `2^bits - c` with `c` small compared to `2^bits`: the bits above `bits` are folded back
+    /// into the low part, multiplied by `c`, since `2^bits = c` modulo the modulus.
+    PseudoMersenne { bits: usize, c: u64 },
+    /// `2^bits - 1`: the high part is added to the low part, without any multiplication.
+    Mersenne { bits: usize },
+}
+
+/// Reduces values modulo a fixed modulus.
+#[derive(Clone, Debug)]
+pub struct Reducer {
+    modulus: BigUint,
+    kind: ReductionKind,
+    /// `2^bits - 1` for the special shapes, 0 otherwise.
+    mask: BigUint,
+}
+
+impl Reducer {
+    /// Detects the shape of the modulus.
+    ///
+    /// **Warning**: panics if the modulus is 0.
+    pub fn new(modulus: BigUint) -> Self {
+        let zero = BigUint::from(0u32);
+        assert!(modulus != zero, "modulus of 0");
+        let kind = if &modulus & (&modulus - 1u32) == zero {
+            ReductionKind::PowerOfTwo {
+                bits: modulus.bits() - 1,
+            }
+        } else {
+            let bits = modulus.bits();
+            let c = (BigUint::from(1u32) << bits) - &modulus;
+            match c.to_u64() {
+                Some(1) => ReductionKind::Mersenne { bits },
+                Some(c) if 2 * (64 - c.leading_zeros() as usize) <= bits => {
+                    ReductionKind::PseudoMersenne { bits, c }
+                }
+                _ => ReductionKind::Generic,
+            }
+        };
+        let mask = match kind {
+            ReductionKind::PowerOfTwo { bits }
+            | ReductionKind::PseudoMersenne { bits, .. }
+            | ReductionKind::Mersenne { bits } => (BigUint::from(1u32) << bits) - 1u32,
+            ReductionKind::Generic => zero,
+        };
+        Reducer {
+            modulus,
+            kind,
+            mask,
+        }
+    }
+
+    pub fn kind(&self) -> ReductionKind {
+        self.kind
+    }
+
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// Returns `x` modulo the modulus.
+    pub fn reduce(&self, x: BigUint) -> BigUint {
+        match self.kind {
+            ReductionKind::PowerOfTwo { .. } => x & &self.mask,
+            ReductionKind::PseudoMersenne { bits, c } => {
+                let mut x = x;
+                while x.bits() > bits {
+                    x = (&x & &self.mask) + (x >> bits) * c;
+                }
+                // x < 2^bits = modulus + c, so one subtraction is enough
+                if x >= self.modulus {
+                    x -= &self.modulus;
+                }
+                x
+            }
+            ReductionKind::Mersenne { bits } => {
+                let mut x = x;
+                while x.bits() > bits {
+                    x = (&x & &self.mask) + (x >> bits);
+                }
+                if x == self.mask {
+                    x = BigUint::from(0u32);
+                }
+                x
+            }
+            ReductionKind::Generic => x % &self.modulus,
+        }
+    }
+}
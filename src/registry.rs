@@ -0,0 +1,43 @@
+//! Runtime registry of the types defined by the macros of this crate, enabled by the `registry`
+//! feature.
+//!
+//! Every type defined with `define_abstract_integer_checked` or `define_refined_modular_integer`
+//! registers an `IntegerType` at link time, so that tooling such as documentation generators can
+//! list the integers and bounds used by a specification:
+//!
+//! ```
+//! for t in abstract_integers::registry::types() {
+//!     println!("{} ({} bits)", t.name, t.bits);
+//! }
+//! ```
+
+use num::BigUint;
+
+/// Description of a type defined by the macros of this crate.
+#[derive(Debug)]
+pub struct IntegerType {
+    /// Name of the type.
+    pub name: &'static str,
+    /// Module in which the type is defined.
+    pub module_path: &'static str,
+    /// Number of bits of the representation.
+    pub bits: usize,
+    /// Name of the base type, for refined types.
+    pub base: Option<&'static str>,
+    #[doc(hidden)]
+    pub __modulus: Option<fn() -> BigUint>,
+}
+
+impl IntegerType {
+    /// The modulus of refined types, `None` for checked types.
+    pub fn modulus(&self) -> Option<BigUint> {
+        self.__modulus.map(|m| m())
+    }
+}
+
+inventory::collect!(IntegerType);
+
+/// Returns all the registered types, in no particular order.
+pub fn types() -> impl Iterator<Item = &'static IntegerType> {
+    inventory::iter::<IntegerType>.into_iter()
+}
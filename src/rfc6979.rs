@@ -0,0 +1,81 @@
+//! Deterministic generation of the nonce `k` of DSA and ECDSA signatures (RFC 6979, section
+//! 3.2), generic over the HMAC implementation.
+//!
+//! The conversions between bit strings and integers follow section 2.3 of the RFC: hashes longer
+//! than the order are truncated to its bit length before being reduced, and the private key and
+//! the reduced hash are encoded on the byte length of the order.
+//!
+//! ```
+//! # use abstract_integers::*;
+//! # use num::{BigUint, CheckedSub, Zero};
+//! # use std::num::ParseIntError;
+//! # use std::ops::*;
+//! # use hmac::Hmac;
+//! # use sha2::{Digest, Sha256};
+//! # define_abstract_integer_checked!(Nat, 256);
+//! # define_refined_modular_integer!(Scalar, Nat, Nat::from_literal(0xffff_ffff_ffff_ffc5));
+//! # let (private_key, msg) = (Scalar::from_literal(42), b"sample");
+//! let k = Scalar::rfc6979_nonce::<Hmac<Sha256>>(private_key, &Sha256::digest(msg));
+//! ```
+
+use digest::{KeyInit, Mac, OutputSizeUser};
+use num::{BigUint, Zero};
+
+/// `bits2int`: the leftmost `qlen` bits of `b` as an integer.
+fn bits2int(b: &[u8], qlen: usize) -> BigUint {
+    let x = BigUint::from_bytes_be(b);
+    if 8 * b.len() > qlen {
+        x >> (8 * b.len() - qlen)
+    } else {
+        x
+    }
+}
+
+/// `int2octets`: the big-endian encoding of `x` on `rolen` bytes.
+fn int2octets(x: &BigUint, rolen: usize) -> Vec<u8> {
+    let bytes = x.to_bytes_be();
+    let mut out = vec![0u8; rolen - bytes.len()];
+    out.extend_from_slice(&bytes);
+    out
+}
+
+fn hmac<M: Mac + KeyInit>(key: &[u8], parts: &[&[u8]]) -> Vec<u8> {
+    let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Returns the nonce in `[1, q-1]` for the private key `x` and the message hash `h1`.
+///
+/// **Warning**: panics if `q` is below 2 or if `x` is not below `q`.
+pub fn generate_k<M: Mac + KeyInit>(x: &BigUint, q: &BigUint, h1: &[u8]) -> BigUint {
+    assert!(q > &BigUint::from(1u32), "no nonce modulo {}", q);
+    assert!(x < q, "private key not below the order");
+    let qlen = q.bits();
+    let rolen = qlen.div_ceil(8);
+    let hlen = <M as OutputSizeUser>::output_size();
+    let z = bits2int(h1, qlen);
+    let z = if &z >= q { z - q } else { z };
+    let (x, h) = (int2octets(x, rolen), int2octets(&z, rolen));
+    let mut v = vec![0x01u8; hlen];
+    let mut k = vec![0x00u8; hlen];
+    k = hmac::<M>(&k, &[&v, &[0x00], &x, &h]);
+    v = hmac::<M>(&k, &[&v]);
+    k = hmac::<M>(&k, &[&v, &[0x01], &x, &h]);
+    v = hmac::<M>(&k, &[&v]);
+    loop {
+        let mut t = Vec::with_capacity(rolen + hlen);
+        while 8 * t.len() < qlen {
+            v = hmac::<M>(&k, &[&v]);
+            t.extend_from_slice(&v);
+        }
+        let nonce = bits2int(&t, qlen);
+        if !nonce.is_zero() && &nonce < q {
+            return nonce;
+        }
+        k = hmac::<M>(&k, &[&v, &[0x00]]);
+        v = hmac::<M>(&k, &[&v]);
+    }
+}
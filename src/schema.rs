@@ -0,0 +1,19 @@
+//! JSON Schema support for the generated types, enabled by the `schemars` feature. The schemas
+//! describe the human-readable serde representation: hex strings without prefix.
+
+use num::BigUint;
+use schemars::{json_schema, Schema};
+
+/// Schema of a hex string representing a value of at most `bytes` bytes, not greater than
+/// `max`. JSON Schema cannot compare hex strings numerically, so the bound is embedded in the
+/// description and in the `x-maximum` extension keyword.
+pub fn hex_schema(type_name: &str, bytes: usize, max: &BigUint) -> Schema {
+    json_schema!({
+        "type": "string",
+        "pattern": "^[0-9a-fA-F]+$",
+        "minLength": 1,
+        "maxLength": 2 * bytes,
+        "description": format!("value of type {} as a hex string, at most 0x{:x}", type_name, max),
+        "x-maximum": format!("{:x}", max),
+    })
+}
@@ -0,0 +1,148 @@
+//! Serde support for the generated types, enabled by the `serde` feature.
+//!
+//! Human-readable formats (JSON, TOML, ...) represent the values as big-endian hex strings,
+//! binary formats as fixed-length big-endian bytes, padded to the byte size of the type. The
+//! `hex` and `bytes` modules force either representation with `#[serde(with = "...")]`:
+//!
+//! ```
+//! # use abstract_integers::*;
+//! # use num::{BigUint, CheckedSub, Zero};
+//! # use std::num::ParseIntError;
+//! # use std::ops::*;
+//! # define_abstract_integer_checked!(BigBounded, 256);
+//! # define_refined_modular_integer!(
+//! #     Felem,
+//! #     BigBounded,
+//! #     BigBounded::pow2(255) - BigBounded::from_literal(19)
+//! # );
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Snapshot {
+//!     #[serde(with = "abstract_integers::serialization::bytes")]
+//!     state: Felem,
+//! }
+//! # let json = serde_json::to_string(&Snapshot { state: Felem::from_literal(1) }).unwrap();
+//! # assert!(json.ends_with("0,1]}"));
+//! ```
+
+use crate::diagnostics;
+use num::BigUint;
+use serde::de::{self, Visitor};
+use serde::{Deserializer, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Conversions used by the serde implementations, implemented by the type definition macros.
+pub trait SerdeInteger: Sized {
+    const TYPE_NAME: &'static str;
+    /// Length of the fixed-size binary representation.
+    const BYTES: usize;
+    /// Whether the type is secret, in which case rejected values are not printed.
+    const SECRET: bool;
+
+    fn to_biguint(&self) -> BigUint;
+
+    /// Returns `None` if the value is not in the range of the type.
+    fn try_from_biguint(x: BigUint) -> Option<Self>;
+}
+
+pub fn serialize<T: SerdeInteger, S: Serializer>(x: &T, s: S) -> Result<S::Ok, S::Error> {
+    if s.is_human_readable() {
+        hex::serialize(x, s)
+    } else {
+        bytes::serialize(x, s)
+    }
+}
+
+pub fn deserialize<'de, T: SerdeInteger, D: Deserializer<'de>>(d: D) -> Result<T, D::Error> {
+    if d.is_human_readable() {
+        hex::deserialize(d)
+    } else {
+        bytes::deserialize(d)
+    }
+}
+
+fn checked<T: SerdeInteger, E: de::Error>(x: BigUint) -> Result<T, E> {
+    let err = diagnostics::OutOfRangeError::new(T::TYPE_NAME, x.clone()).secret(T::SECRET);
+    T::try_from_biguint(x).ok_or_else(|| E::custom(err))
+}
+
+/// Hex string representation, without prefix.
+pub mod hex {
+    use super::*;
+
+    pub fn serialize<T: SerdeInteger, S: Serializer>(x: &T, s: S) -> Result<S::Ok, S::Error> {
+        let mut hex = x.to_biguint().to_str_radix(16);
+        if hex.len() % 2 == 1 {
+            hex.insert(0, '0');
+        }
+        s.serialize_str(&hex)
+    }
+
+    pub fn deserialize<'de, T: SerdeInteger, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<T, D::Error> {
+        d.deserialize_str(HexVisitor(PhantomData))
+    }
+
+    struct HexVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: SerdeInteger> Visitor<'de> for HexVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a hex string representing a value of type {}", T::TYPE_NAME)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+            match BigUint::parse_bytes(v.as_bytes(), 16) {
+                Some(x) => checked(x),
+                None => Err(E::invalid_value(de::Unexpected::Str(v), &self)),
+            }
+        }
+    }
+}
+
+/// Fixed-length big-endian bytes representation.
+pub mod bytes {
+    use super::*;
+
+    pub fn serialize<T: SerdeInteger, S: Serializer>(x: &T, s: S) -> Result<S::Ok, S::Error> {
+        let be = x.to_biguint().to_bytes_be();
+        let mut out = vec![0u8; T::BYTES - be.len()];
+        out.extend_from_slice(&be);
+        s.serialize_bytes(&out)
+    }
+
+    pub fn deserialize<'de, T: SerdeInteger, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<T, D::Error> {
+        d.deserialize_bytes(BytesVisitor(PhantomData))
+    }
+
+    struct BytesVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: SerdeInteger> Visitor<'de> for BytesVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{} big-endian bytes", T::BYTES)
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<T, E> {
+            if v.len() != T::BYTES {
+                return Err(E::invalid_length(v.len(), &self));
+            }
+            checked(BigUint::from_bytes_be(v))
+        }
+
+        fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+            let mut v = Vec::with_capacity(T::BYTES);
+            while let Some(b) = seq.next_element::<u8>()? {
+                v.push(b);
+            }
+            self.visit_bytes(&v)
+        }
+    }
+}
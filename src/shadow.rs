@@ -0,0 +1,87 @@
+//! Differential shadow execution, enabled by the `shadow` feature. Each arithmetic operator of
+//! the generated types recomputes its result with plain `BigUint` arithmetic, following the
+//! definition of the operation, and panics if the two disagree. This is meant to validate
+//! optimized implementations of the operators against the reference semantics, at the cost of
+//! doing every operation twice.
+//!
+//! The checks are skipped for the operations raising an `ArithmeticPanic`.
+
+use crate::traits::{CheckedInteger, ModularInteger};
+use num::BigUint;
+
+/// Whether the crate was compiled with the `shadow` feature.
+pub const ENABLED: bool = cfg!(feature = "shadow");
+
+pub(crate) fn reference(op: &str, a: &BigUint, b: &BigUint) -> BigUint {
+    match op {
+        "+" => a + b,
+        "-" => a - b,
+        "*" => a * b,
+        "/" => a / b,
+        "%" => a % b,
+        _ => unreachable!("no reference semantics for operator {}", op),
+    }
+}
+
+/// Panics with the operands and the two results, or without them for secret types.
+fn mismatch(
+    type_name: &str,
+    secret: bool,
+    op: &str,
+    a: &BigUint,
+    b: &BigUint,
+    got: &BigUint,
+    expected: &BigUint,
+) -> ! {
+    if secret {
+        panic!(
+            "shadow execution mismatch in type {}: {} on redacted secret operands",
+            type_name, op
+        )
+    }
+    panic!(
+        "shadow execution mismatch in type {}: {:#x} {} {:#x} computed {:#x}, expected {:#x}",
+        type_name, a, op, b, got, expected
+    )
+}
+
+/// Checks the result `r` of `a op b` for a checked type.
+#[inline]
+pub fn check_checked<T>(type_name: &str, op: &str, a: T, b: T, r: T)
+where
+    T: CheckedInteger + Into<BigUint>,
+{
+    if !ENABLED {
+        return;
+    }
+    let (a, b, r): (BigUint, BigUint, BigUint) = (a.into(), b.into(), r.into());
+    let expected = reference(op, &a, &b);
+    if r != expected {
+        mismatch(type_name, T::SECRET, op, &a, &b, &r, &expected);
+    }
+}
+
+/// Checks the result `r` of `a op b` for a refined type: the reference result is computed on
+/// the representatives, with subtraction done modulo the modulus.
+#[inline]
+pub fn check_modular<T: ModularInteger + Into<BigUint>>(
+    type_name: &str,
+    op: &str,
+    a: T,
+    b: T,
+    r: T,
+) {
+    if !ENABLED {
+        return;
+    }
+    let m = T::modulus();
+    let (a, b, r): (BigUint, BigUint, BigUint) = (a.into(), b.into(), r.into());
+    let expected = if op == "-" {
+        (&a + &m - &b) % &m
+    } else {
+        reference(op, &a, &b) % &m
+    };
+    if r != expected {
+        mismatch(type_name, T::SECRET, op, &a, &b, &r, &expected);
+    }
+}
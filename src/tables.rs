@@ -0,0 +1,141 @@
+//! Precomputed operation tables for the refined types defined with the `tables` option, whose
+//! modulus must fit in 16 bits.
+//!
+//! The values are handled as native `u16` instead of `BigUint`. Prime moduli get discrete
+//! logarithm and exponential tables, so that a multiplication is two lookups and an addition;
+//! composite moduli multiply natively. Inverses are always a single lookup.
+//!
+//! The lookups are indexed by the operands, whose values would leak through the cache, so the
+//! option is rejected on secret types.
+
+use num::BigUint;
+
+/// Moduli handled by the tables are strictly below this bound.
+pub const MODULUS_BOUND: u32 = 1 << 16;
+
+/// Refined types whose modulus is strictly below this bound build an inversion table on the
+/// first call to `inv`, even without the `tables` option.
+pub const INV_TABLE_BOUND: u32 = 1 << 12;
+
+/// Operation tables of a small modulus, see the module documentation.
+#[derive(Clone, Debug)]
+pub struct OpTables {
+    modulus: u32,
+    /// `log[x]` is the discrete logarithm of `x` in base the generator, empty if the modulus
+    /// is not prime.
+    log: Vec<u16>,
+    /// `exp[i]` is the generator to the power `i`, for `i` below `2 * (modulus - 1)` so that
+    /// the sum of two logarithms needs no reduction.
+    exp: Vec<u16>,
+    /// `inv[x]` is `x^(modulus - 2)`, the inverse of `x` for prime moduli.
+    inv: Vec<u16>,
+}
+
+fn pow_mod(x: u32, mut e: u32, m: u32) -> u32 {
+    let (mut x, mut acc) = (x as u64, 1u64 % m as u64);
+    while e > 0 {
+        if e & 1 == 1 {
+            acc = acc * x % m as u64;
+        }
+        x = x * x % m as u64;
+        e >>= 1;
+    }
+    acc as u32
+}
+
+fn prime_factors(mut n: u32) -> Vec<u32> {
+    let mut factors = Vec::new();
+    let mut d = 2;
+    while d * d <= n {
+        if n.is_multiple_of(d) {
+            factors.push(d);
+            while n.is_multiple_of(d) {
+                n /= d;
+            }
+        }
+        d += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    factors
+}
+
+/// Returns the table of `x^(modulus - 2)` for all `x` below the modulus, the inverses for prime
+/// moduli.
+///
+/// **Warning**: panics if the modulus is below 2 or does not fit in 16 bits.
+pub fn inverse_table(modulus: &BigUint) -> Vec<u16> {
+    assert!(
+        *modulus >= BigUint::from(2u32) && *modulus < BigUint::from(MODULUS_BOUND),
+        "modulus {} is not between 2 and 2^16 for operation tables",
+        modulus
+    );
+    let m = modulus.to_u32_digits()[0];
+    (0..m).map(|x| pow_mod(x, m - 2, m) as u16).collect()
+}
+
+impl OpTables {
+    /// Builds the tables of `modulus`.
+    ///
+    /// **Warning**: panics if the modulus is below 2 or does not fit in 16 bits.
+    pub fn new(modulus: &BigUint) -> Self {
+        let inv = inverse_table(modulus);
+        let m = modulus.to_u32_digits()[0];
+        let (mut log, mut exp) = (Vec::new(), Vec::new());
+        if m > 2 && prime_factors(m) == [m] {
+            let factors = prime_factors(m - 1);
+            let g = (2..m)
+                .find(|g| factors.iter().all(|q| pow_mod(*g, (m - 1) / q, m) != 1))
+                .expect("prime moduli have a generator");
+            log = vec![0u16; m as usize];
+            exp = Vec::with_capacity(2 * (m as usize - 1));
+            let mut x = 1u32;
+            for i in 0..2 * (m - 1) {
+                if i < m - 1 {
+                    log[x as usize] = i as u16;
+                }
+                exp.push(x as u16);
+                x = x * g % m;
+            }
+        }
+        OpTables {
+            modulus: m,
+            log,
+            exp,
+            inv,
+        }
+    }
+
+    /// The modulus of the tables.
+    pub fn modulus(&self) -> u16 {
+        self.modulus as u16
+    }
+
+    /// Returns `a + b` modulo the modulus, for reduced `a` and `b`.
+    pub fn add(&self, a: u16, b: u16) -> u16 {
+        let c = a as u32 + b as u32;
+        (if c >= self.modulus { c - self.modulus } else { c }) as u16
+    }
+
+    /// Returns `a - b` modulo the modulus, for reduced `a` and `b`.
+    pub fn sub(&self, a: u16, b: u16) -> u16 {
+        ((a as u32 + self.modulus - b as u32) % self.modulus) as u16
+    }
+
+    /// Returns `a * b` modulo the modulus, for reduced `a` and `b`.
+    pub fn mul(&self, a: u16, b: u16) -> u16 {
+        if self.log.is_empty() {
+            return (a as u32 * b as u32 % self.modulus) as u16;
+        }
+        if a == 0 || b == 0 {
+            return 0;
+        }
+        self.exp[self.log[a as usize] as usize + self.log[b as usize] as usize]
+    }
+
+    /// Returns `a^(modulus - 2)`, the inverse of `a` for prime moduli, for a reduced `a`.
+    pub fn inv(&self, a: u16) -> u16 {
+        self.inv[a as usize]
+    }
+}
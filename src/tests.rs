@@ -17,7 +17,7 @@ fn arith() {
     let x1 = Felem::from_literal(24875808327634644);
     let x2 = Felem::from_literal(91987276365379830);
     let x3 = x1 + x2;
-    assert_eq!(Felem::from_literal(116863084693014474u128), x3.into())
+    assert_eq!(Felem::from_literal(116863084693014474u128), Into::<Felem>::into(x3))
 }
 
 define_refined_modular_integer!(SmallModular, BigBounded, BigBounded::from_literal(255));
@@ -27,12 +27,12 @@ fn wrapping() {
     let x1 = SmallModular::from_literal(254);
     let x2 = SmallModular::from_literal(3);
     let x3 = x1 + x2;
-    assert_eq!(SmallModular::from_literal(2), x3.into());
+    assert_eq!(SmallModular::from_literal(2), Into::<SmallModular>::into(x3));
     let x4 = SmallModular::from_literal(5);
     let x5 = x3 - x4;
-    assert_eq!(SmallModular::from_literal(252), x5.into());
+    assert_eq!(SmallModular::from_literal(252), Into::<SmallModular>::into(x5));
     let x6 = x5 / SmallModular::from_literal(4);
-    assert_eq!(SmallModular::from_literal(63), x6.into());
+    assert_eq!(SmallModular::from_literal(63), Into::<SmallModular>::into(x6));
 }
 
 define_abstract_integer_checked!(Scalar, 512);
@@ -50,3 +50,1697 @@ fn conversion() {
     let _z = x * y;
     ()
 }
+
+#[test]
+fn limbs() {
+    let x = BigBounded::pow2(130) + BigBounded::from_literal(0x0123_4567_89ab_cdef);
+    assert_eq!(x.as_limbs(), [0x0123_4567_89ab_cdef, 0, 4, 0]);
+    assert_eq!(BigBounded::from_limbs(x.as_limbs()), x);
+    let y = Felem::from_literal(42);
+    assert_eq!(Felem::from_limbs(y.as_limbs()), y);
+}
+
+#[test]
+fn bytes() {
+    let x = SizeNatExample::from_literal(0x0102_0304);
+    assert_eq!(x.as_bytes(), &[0, 0, 0, 0, 1, 2, 3, 4]);
+    assert_eq!(x.into_bytes(), [0, 0, 0, 0, 1, 2, 3, 4]);
+}
+
+define_abstract_integer_checked!(LittleNat, 255, little_endian);
+
+#[test]
+fn little_endian() {
+    let x = LittleNat::from_literal(0x0102);
+    assert_eq!(&x.as_bytes()[..3], &[2, 1, 0]);
+    assert_eq!(LittleNat::from_bytes(&[2, 1]), x);
+    assert_eq!(x.as_limbs(), [0x0102, 0, 0, 0]);
+    assert_eq!(x + LittleNat::from_literal(1), LittleNat::from_literal(0x0103));
+    let big = SizeNatExample::from_literal(0x0102);
+    assert_eq!(SizeNatExample::from_bytes(&[1, 2]), big);
+}
+
+#[test]
+fn ffi() {
+    define_abstract_integer_ffi!(Felem, felem_from_bytes, felem_to_bytes);
+    let mut x = Felem::default();
+    let input = [1u8, 2, 3];
+    assert_eq!(unsafe { felem_from_bytes(input.as_ptr(), input.len(), &mut x) }, 0);
+    assert_eq!(x, Felem::from_literal(0x010203));
+    let mut output = [0u8; 32];
+    unsafe { felem_to_bytes(&x, output.as_mut_ptr()) };
+    assert_eq!(&output[29..], &input);
+    let too_big = [1u8; 33];
+    assert_eq!(unsafe { felem_from_bytes(too_big.as_ptr(), too_big.len(), &mut x) }, -1);
+    assert_eq!(Felem::try_from_bytes(&too_big), None);
+    assert_eq!(Felem::try_from_bytes(&input), Some(x));
+    assert_eq!(SizeNatExample::try_from_bytes(&[0xff; 9]), None);
+    let max = SizeNatExample::from_literal(u64::MAX as u128);
+    assert_eq!(SizeNatExample::try_from_bytes(&[0xff; 8]), Some(max));
+}
+
+#[test]
+fn shifts() {
+    let x = SizeNatExample::from_literal(0b1100);
+    assert_eq!(x.checked_shl(60), Some(SizeNatExample::pow2(63) + SizeNatExample::pow2(62)));
+    assert_eq!(x.checked_shl(61), None);
+    assert_eq!(x.checked_shl(usize::MAX), None);
+    let zero = SizeNatExample::from_literal(0);
+    assert_eq!(zero.checked_shl(usize::MAX), Some(zero));
+    assert_eq!(x.checked_shr(u32::MAX as usize), None);
+    assert_eq!(zero.checked_shr(u32::MAX as usize), Some(zero));
+    assert_eq!(x.checked_shr(2), Some(SizeNatExample::from_literal(0b11)));
+    assert_eq!(x.checked_shr(3), None);
+    assert_eq!(x << 4, SizeNatExample::from_literal(0b1100_0000));
+    assert_eq!(x >> 2, SizeNatExample::from_literal(0b11));
+}
+
+#[test]
+#[should_panic]
+fn shift_overflow() {
+    let _ = SizeNatExample::from_literal(1) << 64;
+}
+
+define_refined_modular_integer!(Gf257, BigBounded, BigBounded::from_literal(257));
+
+#[test]
+fn order() {
+    let factors = [(BigBounded::from_literal(2), 8)];
+    assert_eq!(Gf257::from_literal(3).order(&factors), BigBounded::from_literal(256));
+    assert!(Gf257::from_literal(3).is_generator(&factors));
+    assert_eq!(Gf257::from_literal(2).order(&factors), BigBounded::from_literal(16));
+    assert!(!Gf257::from_literal(2).is_generator(&factors));
+    assert!(!Gf257::from_literal(0).is_generator(&factors));
+}
+
+#[test]
+fn roots_of_unity() {
+    assert_eq!(Gf257::primitive_root(), Gf257::from_literal(3));
+    let w = Gf257::root_of_unity(16);
+    assert_eq!(w.pow(16), Gf257::from_literal(1));
+    assert_ne!(w.pow(8), Gf257::from_literal(1));
+    let factors: Vec<(SizeNatExample, u32)> =
+        [(2, 1), (3, 2), (5, 2), (7, 1), (11, 1), (13, 1), (31, 1), (41, 1), (61, 1), (151, 1), (331, 1), (1321, 1)]
+            .iter()
+            .map(|(q, e)| (SizeNatExample::from_literal(*q), *e))
+            .collect();
+    let g = SizeNatFieldExample::primitive_root_with_factors(&factors);
+    assert_eq!(g, SizeNatFieldExample::primitive_root());
+    assert_eq!(g, SizeNatFieldExample::from_literal(37));
+    let w = SizeNatFieldExample::root_of_unity_with_factors(25, &factors);
+    assert_eq!(w.pow(25), SizeNatFieldExample::from_literal(1));
+    assert_ne!(w.pow(5), SizeNatFieldExample::from_literal(1));
+}
+
+define_abstract_integers! { U8: 8, U16: 16, U32: 32 }
+
+#[test]
+fn overflow_bound() {
+    let x = U8::from_literal(255);
+    assert!(std::panic::catch_unwind(|| x + U8::from_literal(1)).is_err());
+    assert!(std::panic::catch_unwind(|| U8::from_literal(256)).is_err());
+}
+
+#[test]
+fn batch_definition() {
+    assert_eq!(U8::from_literal(255).into_bytes().len(), 1);
+    assert_eq!(U16::from_literal(256).into_bytes(), [1, 0]);
+    assert_eq!(U32::from_literal(3) * U32::from_literal(5), U32::from_literal(15));
+}
+
+define_abstract_integer_checked!(mod p256 { Felem, 256 });
+define_refined_modular_integer!(mod p256_field { Felem, BigBounded, BigBounded::pow2(255) - BigBounded::from_literal(19) });
+
+#[test]
+fn modules() {
+    let x = p256::Felem::from_literal(5) + p256::Felem::from_literal(7);
+    assert_eq!(x, p256::Felem::from_literal(12));
+    let y = p256_field::Felem::from_literal(5) - p256_field::Felem::from_literal(7);
+    let z: BigBounded = (Felem::from_literal(5) - Felem::from_literal(7)).into();
+    assert_eq!(BigBounded::from(y), z);
+}
+
+#[test]
+fn arithmetic_panic() {
+    use crate::diagnostics::{ArithmeticErrorKind, ArithmeticPanic};
+    let payload = std::panic::catch_unwind(|| {
+        SizeNatExample::from_literal(3) - SizeNatExample::from_literal(0x10)
+    })
+    .unwrap_err();
+    let payload = payload.downcast_ref::<ArithmeticPanic>().unwrap();
+    assert_eq!(payload.kind, ArithmeticErrorKind::Underflow);
+    assert_eq!(
+        payload.to_string(),
+        "bounded underflow in type SizeNatExample: 0x3 - 0x10"
+    );
+    let payload = std::panic::catch_unwind(|| {
+        SizeNatExample::pow2(63) + SizeNatExample::pow2(63)
+    })
+    .unwrap_err();
+    let payload = payload.downcast_ref::<ArithmeticPanic>().unwrap();
+    assert_eq!(payload.kind, ArithmeticErrorKind::Overflow);
+}
+
+define_abstract_integer_checked!(HookedNat, 8);
+
+#[test]
+fn overflow_hook() {
+    crate::diagnostics::set_type_overflow_hook::<HookedNat, _>(|error| {
+        if error.operation == "+" {
+            Some(BigUint::from(255u32))
+        } else {
+            None
+        }
+    });
+    let x = HookedNat::from_literal(200) + HookedNat::from_literal(100);
+    assert_eq!(x, HookedNat::from_literal(255));
+    assert!(std::panic::catch_unwind(|| HookedNat::from_literal(1) - HookedNat::from_literal(2)).is_err());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "invariant violated")]
+fn invariant_checking() {
+    let corrupted: LittleNat = unsafe { std::mem::transmute([0xffu8; 32]) };
+    let _ = corrupted + LittleNat::from_literal(1);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "invariant violated")]
+fn refined_invariant_checking() {
+    let corrupted: Gf257 = unsafe { std::mem::transmute(BigBounded::from_literal(300)) };
+    let _ = corrupted + Gf257::from_literal(1);
+}
+
+#[test]
+fn canonical() {
+    let p = Gf257::max();
+    let values = [
+        Gf257::from(p),
+        Gf257::from(p + BigBounded::from_literal(3)),
+        Gf257::from_hex("0104"),
+        Gf257::from_bytes(&[1, 4]),
+        Gf257::from_bytes_le(&[4, 1]),
+        Gf257::from_limbs([260, 0, 0, 0]),
+        Gf257::from_literal(256) + Gf257::from_literal(4),
+        Gf257::from_literal(1) - Gf257::from_literal(2),
+        Gf257::from_literal(200) * Gf257::from_literal(200),
+    ];
+    for x in values.iter() {
+        assert!(x.is_canonical());
+    }
+    assert_eq!(values[1], Gf257::from_literal(3));
+    assert_eq!(values[2], values[1]);
+    assert!(std::panic::catch_unwind(|| Gf257::from_literal(257)).is_err());
+}
+
+#[test]
+fn from_base_reduces() {
+    let unreduced = Gf257::max() * BigBounded::from_literal(5) + BigBounded::from_literal(12);
+    let x = Gf257::from(unreduced);
+    assert_eq!(BigBounded::from(x), BigBounded::from_literal(12));
+    assert_eq!(x, Gf257::from_literal(12));
+    assert_eq!(Gf257::from(Gf257::max()), Gf257::default());
+}
+
+#[test]
+fn ladder_exponentiation() {
+    let x = Felem::from_literal(0x1234_5678_9abc_def0);
+    for exp in [0u128, 1, 2, 3, 0xffff, 1 << 127, u128::MAX].iter() {
+        assert_eq!(x.pow_ct(*exp), x.pow(*exp));
+    }
+    let m = SizeNatExample::from_literal(1_000_003);
+    let y = SizeNatExample::from_literal(123_456_789);
+    assert_eq!(y.pow_ct(65537, m), y.pow(65537, m));
+}
+
+#[test]
+fn pow_table() {
+    let g = Felem::from_literal(9);
+    let table = PowTable::new(g, 128);
+    for exp in [0u128, 1, 15, 16, 0xdead_beef, u128::MAX].iter() {
+        assert_eq!(table.pow(&BigUint::from(*exp)), g.pow(*exp));
+    }
+    let table = PowTable::with_window(g, 20, 3);
+    assert_eq!(table.pow(&BigUint::from(0xfffffu32)), g.pow(0xfffff));
+}
+
+#[test]
+fn multi_exponentiation() {
+    let bases = [Felem::from_literal(2), Felem::from_literal(3), Felem::from_literal(5)];
+    let exps = [Gf257::from_literal(200), Gf257::from_literal(0), Gf257::from_literal(17)];
+    let expected = bases[0].pow(200) * bases[2].pow(17);
+    assert_eq!(multi_pow(&bases, &exps), expected);
+    let exps = [SizeNatExample::from_literal(u64::MAX as u128), SizeNatExample::from_literal(1), SizeNatExample::from_literal(7)];
+    let expected = bases[0].pow(u64::MAX as u128) * bases[1] * bases[2].pow(7);
+    assert_eq!(multi_pow(&bases, &exps), expected);
+}
+
+#[test]
+fn inner_product() {
+    let a: Vec<Gf257> = (250..256).map(Gf257::from_literal).collect();
+    let b: Vec<Gf257> = (100..106).map(Gf257::from_literal).collect();
+    let expected = a.iter().zip(b.iter()).fold(Gf257::from_literal(0), |acc, (x, y)| acc + *x * *y);
+    assert_eq!(Gf257::inner_product(&a, &b), expected);
+    assert_eq!(Gf257::inner_product(&[], &[]), Gf257::from_literal(0));
+}
+
+#[test]
+fn horner() {
+    let coeffs: Vec<Gf257> = [3, 0, 2, 1].iter().map(|c| Gf257::from_literal(*c)).collect();
+    let x = Gf257::from_literal(10);
+    assert_eq!(Gf257::horner(&coeffs, x), Gf257::from_literal((3 + 200 + 1000) % 257));
+    assert_eq!(crate::polynomial::horner(&coeffs[..1], x), Gf257::from_literal(3));
+    assert_eq!(Gf257::horner(&[], x), Gf257::from_literal(0));
+}
+
+define_abstract_integer_checked!(
+    /// A hashable 128-bit natural.
+    #[derive(Hash)]
+    HashableNat,
+    128
+);
+define_refined_modular_integer!(#[derive(Hash)] HashableField, HashableNat, HashableNat::from_literal(101));
+
+#[test]
+fn attribute_passthrough() {
+    let mut set = std::collections::HashSet::new();
+    set.insert(HashableField::from_literal(3));
+    set.insert(HashableField::from_literal(104 % 101));
+    set.insert(HashableField::from_literal(4));
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&HashableField::from_literal(4)));
+}
+
+#[test]
+fn parity() {
+    assert!(SizeNatExample::from_literal(6).is_even());
+    assert!(SizeNatExample::from_literal(7).is_odd());
+    assert!(LittleNat::from_literal(0x0101).lsb());
+    assert!(!LittleNat::from_literal(0x0100).lsb());
+    assert!((Gf257::from_literal(0) - Gf257::from_literal(1)).is_even());
+    assert!(Gf257::from_literal(255).is_odd());
+}
+
+#[test]
+fn two_adic() {
+    assert_eq!(SizeNatExample::from_literal(0b1011000).trailing_zeros(), 3);
+    assert_eq!(SizeNatExample::from_literal(0).trailing_zeros(), 64);
+    assert_eq!(LittleNat::pow2(200).trailing_zeros(), 200);
+    assert_eq!(Gf257::from_literal(256).trailing_zeros(), 8);
+    let (s, q) = BigBounded::from_literal(257).decompose_2adic();
+    assert_eq!((s, q), (8, BigBounded::from_literal(1)));
+    let (s, q) = SizeNatExample::from_literal(2 * 2 * 2 * 5 * 3 + 1).decompose_2adic();
+    assert_eq!((s, q), (3, SizeNatExample::from_literal(15)));
+}
+
+#[test]
+fn perfect_powers() {
+    assert_eq!(SizeNatExample::from_literal(1000).nth_root(3), SizeNatExample::from_literal(10));
+    assert_eq!(SizeNatExample::from_literal(999).nth_root(3), SizeNatExample::from_literal(9));
+    assert_eq!(BigBounded::pow2(200).nth_root(2), BigBounded::pow2(100));
+    assert!(BigBounded::from_literal(7).pow(13, BigBounded::pow2(255)).is_perfect_power());
+    assert!(SizeNatExample::from_literal(1 << 40).is_perfect_power());
+    assert!(!SizeNatExample::from_literal(1_000_003).is_perfect_power());
+    assert!(!SizeNatExample::from_literal(1).is_perfect_power());
+    assert!(!SizeNatExample::from_literal(432).is_perfect_power());
+}
+
+define_abstract_integer_checked!(Narrow, 100, wide);
+
+#[test]
+fn mul_wide() {
+    let x = Narrow::pow2(99) + Narrow::from_literal(3);
+    let y = Narrow::pow2(98);
+    assert_eq!(x.mul_wide(y), NarrowWide::pow2(197) + NarrowWide::from_literal(3) * NarrowWide::pow2(98));
+    assert_eq!(NarrowWide::LIMBS, 4);
+}
+
+define_double_width!(BigBounded, HashableNat);
+
+#[test]
+fn double_width() {
+    use std::convert::TryFrom;
+    let x = HashableNat::from_literal(u128::MAX);
+    let wide = BigBounded::from(x);
+    assert_eq!(HashableNat::try_from(wide), Ok(x));
+    assert!(HashableNat::try_from(wide + BigBounded::from_literal(1)).is_err());
+    let (high, low) = (wide * BigBounded::pow2(64)).split();
+    assert_eq!(high, HashableNat::from_literal(u64::MAX as u128));
+    assert_eq!(low, HashableNat::from_literal((u64::MAX as u128) << 64));
+    assert_eq!(BigBounded::combine(high, low), wide * BigBounded::pow2(64));
+    let (high, low) = Narrow::pow2(99).mul_wide(Narrow::pow2(2)).split();
+    assert_eq!((high, low), (Narrow::from_literal(2), Narrow::from_literal(0)));
+}
+
+define_abstract_integer_checked!(Bit, 1);
+define_abstract_integer_checked!(FieldRepr, 255);
+define_bit_concat!(BigBounded, Bit, FieldRepr);
+
+#[test]
+fn bit_concat() {
+    let y = FieldRepr::pow2(254) + FieldRepr::from_literal(5);
+    let encoded = BigBounded::concat(Bit::from_literal(1), y);
+    assert_eq!(encoded, BigBounded::pow2(255) + BigBounded::pow2(254) + BigBounded::from_literal(5));
+    assert_eq!(encoded.split_at(255), (Bit::from_literal(1), y));
+    assert!(std::panic::catch_unwind(|| encoded.split_at(200)).is_err());
+}
+
+#[test]
+fn from_primitives() {
+    use std::convert::TryFrom;
+    let x: Gf257 = 300u32.into();
+    assert_eq!(x, Gf257::from_literal(43));
+    assert_eq!(Gf257::from(usize::MAX), Gf257::from_literal(u64::MAX as u128 % 257));
+    let y = SizeNatExample::try_from(u64::MAX).unwrap();
+    assert_eq!(y, SizeNatExample::from_literal(u64::MAX as u128));
+    let sum = U8::try_from(200u8).unwrap() + U8::try_from(55u16).unwrap();
+    assert_eq!(sum, U8::from_literal(255));
+    assert_eq!(U8::try_from(256u32).unwrap_err().value, BigUint::from(256u32));
+}
+
+#[test]
+fn ordering() {
+    let mut values: Vec<LittleNat> = [0x0200u128, 0x01ff, 0x0001, 0x0100].iter().map(|x| LittleNat::from_literal(*x)).collect();
+    values.sort();
+    let sorted: Vec<LittleNat> = [0x0001u128, 0x0100, 0x01ff, 0x0200].iter().map(|x| LittleNat::from_literal(*x)).collect();
+    assert_eq!(values, sorted);
+    assert!(SizeNatExample::from_literal(0x0100) > SizeNatExample::from_literal(0xff));
+    assert_eq!(BigBounded::pow2(100), BigBounded::pow2(100));
+}
+
+define_bounded_index!(Round, 10);
+
+#[test]
+fn bounded_index() {
+    let mut keys = [0u32; 10];
+    for r in Round::all() {
+        *keys.at_mut(r) = r.as_usize() as u32 * 2;
+    }
+    assert_eq!(*keys.at(Round::from_literal(7)), 14);
+    assert_eq!(keys[Round::from_literal(7)], 14);
+    let longer = [1u8; 12];
+    assert_eq!(*longer.at(Round::from_literal(9)), 1);
+    assert_eq!(longer[..][Round::from_literal(3)], 1);
+    assert!(std::panic::catch_unwind(|| Round::from_literal(10)).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_representations() {
+    use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+    let x = HashableNat::from_literal(0xabc);
+    assert_tokens(&x.readable(), &[Token::Str("0abc")]);
+    assert_tokens(
+        &x.compact(),
+        &[Token::Bytes(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x0a, 0xbc])],
+    );
+    let y = HashableField::from_literal(100);
+    assert_tokens(&y.readable(), &[Token::Str("64")]);
+    assert_de_tokens_error::<serde_test::Readable<HashableField>>(
+        &[Token::Str("65")],
+        "value 0x65 out of the range of type HashableField",
+    );
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn json_schema() {
+    let schema = schemars::schema_for!(HashableField);
+    assert_eq!(schema.get("type"), Some(&serde_json::json!("string")));
+    assert_eq!(schema.get("maxLength"), Some(&serde_json::json!(32)));
+    assert_eq!(schema.get("x-maximum"), Some(&serde_json::json!("64")));
+    let schema = schemars::schema_for!(HashableNat);
+    assert_eq!(schema.get("maxLength"), Some(&serde_json::json!(32)));
+    assert_eq!(
+        schema.get("x-maximum"),
+        Some(&serde_json::json!("ffffffffffffffffffffffffffffffff"))
+    );
+}
+
+#[test]
+fn algebraic_laws() {
+    laws::assert_ring_axioms::<SmallModular>();
+    let mut state = 0x1234_5678_9abc_def0u64;
+    laws::assert_field_axioms::<Felem, _>(|| {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let x = BigUint::from(state);
+        Felem::from(&x * &x * &x * &x)
+    });
+    assert!(std::panic::catch_unwind(|| {
+        laws::assert_field_axioms::<SmallModular, _>(|| SmallModular::from(7u8))
+    })
+    .is_err());
+}
+
+#[cfg(feature = "shadow")]
+#[test]
+fn shadow_execution() {
+    let x = Gf257::from_literal(200) + Gf257::from_literal(100) - Gf257::from_literal(250);
+    assert_eq!(x, Gf257::from_literal(50));
+    let _ = BigBounded::from_literal(7) * BigBounded::from_literal(6) % BigBounded::from_literal(5);
+    let forged = Gf257::from_literal(1);
+    assert!(std::panic::catch_unwind(|| {
+        shadow::check_modular("Gf257", "*", Gf257::from_literal(2), Gf257::from_literal(3), forged)
+    })
+    .is_err());
+}
+
+#[test]
+fn fuzzing_helpers() {
+    let inputs: [&[u8]; 5] = [
+        &[],
+        &[0xff; 8],
+        &[0x01, 0x00, 0x00, 0x03],
+        &[0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde],
+        &[0xff; 80],
+    ];
+    for data in inputs.iter() {
+        fuzzing::fuzz_checked::<SizeNatExample>(data);
+        fuzzing::fuzz_checked::<U8>(data);
+        fuzzing::fuzz_modular::<SmallModular>(data);
+        fuzzing::fuzz_field::<Gf257>(data);
+        fuzzing::fuzz_field::<Felem>(data);
+    }
+}
+
+#[test]
+fn slice_arithmetic() {
+    let a: Vec<Gf257> = [1u32, 100, 256, 0].iter().map(|&x| Gf257::from(x)).collect();
+    let b: Vec<Gf257> = [2u32, 200, 3, 5].iter().map(|&x| Gf257::from(x)).collect();
+    let mut dst = vec![Gf257::from(0u8); 4];
+    Gf257::add_slices(&mut dst, &a, &b);
+    assert_eq!(dst, vec![Gf257::from(3u32), Gf257::from(43u32), Gf257::from(2u32), Gf257::from(5u32)]);
+    Gf257::sub_slices(&mut dst, &a, &b);
+    assert_eq!(dst, (0..4).map(|i| a[i] - b[i]).collect::<Vec<_>>());
+    Gf257::mul_slices(&mut dst, &a, &b);
+    assert_eq!(dst, (0..4).map(|i| a[i] * b[i]).collect::<Vec<_>>());
+    Gf257::scale_slice(&mut dst, &a, Gf257::from(256u32));
+    assert_eq!(dst, (0..4).map(|i| a[i] * Gf257::from(256u32)).collect::<Vec<_>>());
+    assert!(std::panic::catch_unwind(|| Gf257::add_slices(&mut [Gf257::from(0u8)], &a, &b)).is_err());
+}
+
+define_abstract_integer_checked!(SecretKey, 256, secret);
+define_refined_modular_integer!(SecretScalar, SecretKey, SecretKey::from_literal(1009), secret);
+
+#[test]
+fn secret_redaction() {
+    let k = SecretKey::from_literal(123456789);
+    assert_eq!(format!("{}", k), "<redacted 256-bit secret>");
+    assert_eq!(format!("{:?}", k), "<redacted 256-bit secret>");
+    assert_eq!(format!("{:?}", SecretScalar::from_literal(5)), "<redacted 256-bit secret>");
+    assert_eq!(BigUint::from(k), BigUint::from(123456789u32));
+    assert_eq!(format!("{:?}", BigBounded::from_literal(5)), "5");
+    let payload = std::panic::catch_unwind(|| k - SecretKey::from_literal(123456790)).unwrap_err();
+    let payload = payload.downcast_ref::<crate::diagnostics::ArithmeticPanic>().unwrap();
+    assert!(payload.secret && payload.operands.is_empty());
+    assert_eq!(
+        payload.to_string(),
+        "bounded underflow in type SecretKey: - on redacted secret operands"
+    );
+    let payload = std::panic::catch_unwind(|| SecretScalar::from_literal(1010)).unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<String>().unwrap(),
+        "literal <redacted> too big for type SecretScalar"
+    );
+    let payload =
+        std::panic::catch_unwind(|| SecretScalar::from_bytes_minimal(&[3, 0xf1])).unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<String>().unwrap(),
+        "<redacted> is not below the modulus of type SecretScalar"
+    );
+    let error = crate::diagnostics::OutOfRangeError::new("SecretKey", k.into()).secret(true);
+    assert_eq!(error.value, BigUint::from(0u32));
+    assert_eq!(error.to_string(), "secret value out of the range of type SecretKey");
+    let error = crate::diagnostics::NotAUnitError::new("SecretUnits", BigUint::from(7u32));
+    assert_eq!(error.secret(true).to_string(), "secret value is not a unit of SecretUnits");
+}
+
+#[cfg(feature = "subtle")]
+#[test]
+fn constant_time_equality() {
+    let tag = BigBounded::from_hex("00ff10");
+    assert!(bool::from(tag.ct_eq(&BigBounded::from_literal(0xff10))));
+    assert!(!bool::from(tag.ct_eq(&BigBounded::from_literal(0xff11))));
+    assert!(bool::from(LittleNat::from_literal(3).ct_eq(&LittleNat::from_literal(3))));
+    let x = Gf257::from_literal(256);
+    assert!(bool::from(x.ct_eq(&(Gf257::from_literal(0) - Gf257::from_literal(1)))));
+    assert!(!bool::from(x.ct_eq(&Gf257::from_literal(1))));
+}
+
+#[cfg(feature = "timing")]
+#[test]
+fn timing_harness() {
+    let report = timing::measure(
+        BigBounded::from_literal(0),
+        || BigBounded::pow2(200),
+        |x| {
+            let mut y = x;
+            let mut i = 0;
+            while y != BigBounded::from_literal(0) && i < 100 {
+                y = y / BigBounded::from_literal(1) - BigBounded::from_literal(1);
+                i += 1;
+            }
+            y
+        },
+        1000,
+    );
+    assert!(report.leaks(), "{}", report);
+    assert!(report.samples.0 + report.samples.1 <= 1000);
+}
+
+#[test]
+fn variable_time_views() {
+    let k = SecretKey::from_literal(1000);
+    let p = SecretKey::from_literal(1009);
+    assert_eq!(k.var_time() / SecretKey::from_literal(3), SecretKey::from_literal(333));
+    assert_eq!(k.var_time() % SecretKey::from_literal(3), SecretKey::from_literal(1));
+    let i = k.var_time().inv(p);
+    assert_eq!(BigUint::from(k) * BigUint::from(i) % 1009u32, BigUint::from(1u32));
+    assert_eq!(k.var_time().pow(2, p), k.pow_ct(2, p));
+    assert_eq!(format!("{}", k.var_time()), "1000");
+    let s = SecretScalar::from_literal(1000);
+    assert_eq!(s.var_time().inv(), SecretScalar::from(i));
+    assert_eq!(s.inv_ct(), SecretScalar::from(i));
+    assert_eq!(ModularInteger::inv(s), SecretScalar::from(i));
+    assert_eq!(s.var_time().pow(3), ModularInteger::pow(s, 3));
+    assert_eq!(s.var_time() / SecretScalar::from_literal(3), SecretScalar::from_literal(333));
+    assert_eq!(s.var_time().pow_mod_signed(-1), s.inv_ct());
+}
+
+define_refined_modular_integer!(Gf257Div, BigBounded, BigBounded::from_literal(257), field_div);
+
+#[test]
+fn division_semantics() {
+    let (a, b) = (Gf257::from_literal(10), Gf257::from_literal(4));
+    assert_eq!(a / b, Gf257::from_literal(2));
+    assert_eq!(a.div_floor(b), a / b);
+    assert_eq!(a.div_field(b) * b, a);
+    let (a, b) = (Gf257Div::from_literal(10), Gf257Div::from_literal(4));
+    assert_eq!((a / b) * b, a);
+    assert_eq!(a / b, a.div_field(b));
+    assert_eq!(a.div_floor(b), Gf257Div::from_literal(2));
+    assert!(std::panic::catch_unwind(|| a / Gf257Div::from_literal(0)).is_err());
+    fuzzing::fuzz_field::<Gf257Div>(&[0x12, 0x34, 0x56, 0x78]);
+}
+
+#[test]
+fn montgomery_slices() {
+    let xs: Vec<Felem> = (0u32..20).map(|i| Felem::from(i * 7919)).collect();
+    let mut mont = vec![Felem::from(0u8); 20];
+    Felem::to_montgomery_slice(&mut mont, &xs);
+    let r = Felem::from(BigUint::from(1u32) << 256);
+    assert_eq!(mont[3], xs[3] * r);
+    let mut back = vec![Felem::from(0u8); 20];
+    Felem::from_montgomery_slice(&mut back, &mont);
+    assert_eq!(back, xs);
+    let small = [SmallModular::from(200u8)];
+    let mut mont = [SmallModular::from(0u8)];
+    SmallModular::to_montgomery_slice(&mut mont, &small);
+    assert_eq!(mont[0], small[0] * SmallModular::from(BigUint::from(1u32) << 256));
+    let mut back = [SmallModular::from(0u8)];
+    SmallModular::from_montgomery_slice(&mut back, &mont);
+    assert_eq!(back, small);
+}
+
+define_refined_modular_integer!(Wrapping130, BigBounded, BigBounded::pow2(130));
+
+#[test]
+fn power_of_two_reduction() {
+    use reduction::ReductionKind;
+    assert_eq!(Wrapping130::reducer().kind(), ReductionKind::PowerOfTwo { bits: 130 });
+    assert_eq!(Gf257::reducer().kind(), ReductionKind::Generic);
+    let x = Wrapping130::from(BigUint::from(1u32) << 129);
+    assert_eq!(x + x, Wrapping130::from_literal(0));
+    assert_eq!(x * Wrapping130::from_literal(3), x);
+    let minus_one = Wrapping130::from((BigUint::from(1u32) << 130) - 1u32);
+    assert_eq!(Wrapping130::from_literal(0) - Wrapping130::from_literal(1), minus_one);
+    laws::assert_ring_axioms::<Wrapping130>();
+}
+
+define_refined_modular_integer!(Poly1305Field, BigBounded, BigBounded::pow2(130) - BigBounded::from_literal(5));
+
+#[test]
+fn pseudo_mersenne_reduction() {
+    use reduction::ReductionKind;
+    assert_eq!(Felem::reducer().kind(), ReductionKind::PseudoMersenne { bits: 255, c: 19 });
+    assert_eq!(Poly1305Field::reducer().kind(), ReductionKind::PseudoMersenne { bits: 130, c: 5 });
+    assert_eq!(Gf257::reducer().kind(), ReductionKind::Generic);
+    let p: BigUint = (BigUint::from(1u32) << 130) - 5u32;
+    let x = BigUint::from(0xdead_beef_u64) << 200;
+    assert_eq!(BigUint::from(Poly1305Field::from(x.clone())), &x % &p);
+    assert_eq!(BigUint::from(Poly1305Field::from(p.clone())), BigUint::from(0u32));
+    let m = Poly1305Field::from(&p - 1u32);
+    assert_eq!(BigUint::from(m * m), BigUint::from(1u32));
+    laws::assert_field_axioms::<Poly1305Field, _>(|| m.pow(7) + Poly1305Field::from_literal(3));
+}
+
+#[test]
+fn mersenne_reduction() {
+    use reduction::ReductionKind;
+    assert_eq!(SizeNatFieldExample::reducer().kind(), ReductionKind::Mersenne { bits: 61 });
+    let p: BigUint = (BigUint::from(1u32) << 61) - 1u32;
+    for x in [p.clone(), &p * 5u32, &p * &p - 1u32, (&p << 3) + 7u32, BigUint::from(12345u32)].iter() {
+        assert_eq!(BigUint::from(SizeNatFieldExample::from(x.clone())), x % &p);
+    }
+    let m = SizeNatFieldExample::from(&p - 1u32);
+    assert_eq!(m * m, SizeNatFieldExample::from_literal(1));
+    laws::assert_ring_axioms::<SizeNatFieldExample>();
+}
+
+#[test]
+fn minimal_bytes() {
+    assert_eq!(BigBounded::from_literal(0).to_bytes_minimal(), Vec::<u8>::new());
+    assert_eq!(BigBounded::from_literal(0x1234).to_bytes_minimal(), vec![0x12, 0x34]);
+    assert_eq!(LittleNat::from_literal(0x1234).to_bytes_minimal(), vec![0x12, 0x34]);
+    assert_eq!(BigBounded::from_bytes_minimal(&[0x12, 0x34]), BigBounded::from_literal(0x1234));
+    assert_eq!(BigBounded::from_bytes_minimal(&[]), BigBounded::from_literal(0));
+    assert!(std::panic::catch_unwind(|| BigBounded::from_bytes_minimal(&[0, 1])).is_err());
+    assert!(std::panic::catch_unwind(|| U8::from_bytes_minimal(&[1, 0])).is_err());
+    assert_eq!(Gf257::from_literal(256).to_bytes_minimal(), vec![1, 0]);
+    assert_eq!(Gf257::from_bytes_minimal(&[1, 0]), Gf257::from_literal(256));
+    assert!(std::panic::catch_unwind(|| Gf257::from_bytes_minimal(&[1, 1])).is_err());
+}
+
+#[test]
+fn negative_literals() {
+    assert_eq!(Gf257::from_signed_literal(-1), Gf257::from_literal(256));
+    assert_eq!(Gf257::from_signed_literal(-3) + Gf257::from_literal(3), Gf257::from_literal(0));
+    assert_eq!(Gf257::from_signed_literal(5), Gf257::from_literal(5));
+    assert_eq!(Felem::from_signed_literal(-1) * Felem::from_signed_literal(-1), Felem::from_literal(1));
+    assert_eq!(Gf257::from_signed_literal(-257), Gf257::from_literal(0));
+    assert_eq!(Gf257::from_signed_literal(-260), Gf257::from_literal(254));
+    assert_eq!(Gf257::from_signed_literal(300), Gf257::from_literal(43));
+    let min = BigUint::from(1u32) << 127;
+    let expected = 257u32 - (min % 257u32).to_u32_digits()[0];
+    assert_eq!(Gf257::from_signed_literal(i128::MIN), Gf257::from_literal(expected as u128));
+}
+
+#[test]
+fn successor_predecessor() {
+    let n = U8::from_literal(41);
+    assert_eq!(n.inc(), U8::from_literal(42));
+    assert_eq!(n.dec(), U8::from_literal(40));
+    assert_eq!(U8::from_literal(255).wrapping_inc(), U8::from_literal(0));
+    assert_eq!(U8::from_literal(254).wrapping_inc(), U8::from_literal(255));
+    assert!(std::panic::catch_unwind(|| U8::from_literal(255).inc()).is_err());
+    assert!(std::panic::catch_unwind(|| U8::from_literal(0).dec()).is_err());
+    assert_eq!(Gf257::from_literal(256).inc(), Gf257::from_literal(0));
+    assert_eq!(Gf257::from_literal(0).dec(), Gf257::from_literal(256));
+}
+
+#[test]
+fn powers_iterator() {
+    let x = Gf257::from_literal(3);
+    let p: Vec<Gf257> = x.powers().take(5).collect();
+    assert_eq!(p, (0..5).map(|i| x.pow(i)).collect::<Vec<_>>());
+    assert_eq!(powers(x).nth(256), Some(Gf257::from_literal(1)));
+    assert_eq!(powers(Felem::from_literal(0)).take(3).collect::<Vec<_>>(), vec![Felem::from_literal(1), Felem::from_literal(0), Felem::from_literal(0)]);
+}
+
+define_refined_modular_integer!(Gf257Exponent, BigBounded, BigBounded::from_literal(256));
+
+#[test]
+fn scalar_exponents() {
+    let g = Gf257::from_literal(3);
+    let e = Gf257Exponent::from_literal(200);
+    assert_eq!(g.pow_scalar(e), g.pow(200));
+    assert_eq!(g.pow_scalar(e + Gf257Exponent::from_literal(100)), g.pow(44));
+    assert_eq!(g.pow_scalar(BigBounded::from_literal(1000)), g.pow(1000));
+}
+
+define_curve_fields!(
+    P256,
+    base = "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+    scalar = "0xffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+);
+
+#[test]
+fn curve_fields() {
+    assert_eq!(P256Nat::BITS, 256);
+    assert_eq!(__hex_bits("0x0013"), 5);
+    assert_eq!(__hex_bits("0"), 0);
+    let p_minus_one = P256Base::from_literal(0) - P256Base::from_literal(1);
+    assert_eq!(P256Base::from_literal(1) + p_minus_one, P256Base::from_literal(0));
+    let k = P256Scalar::reduce_scalar_from_base(p_minus_one);
+    let n = BigUint::parse_bytes(b"ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551", 16).unwrap();
+    assert_eq!(BigUint::from(k), BigUint::from(p_minus_one) - n);
+    assert_eq!(P256Scalar::reducer().kind(), reduction::ReductionKind::Generic);
+}
+
+#[test]
+fn x25519_ladder() {
+    let hex_le = |s: &str| {
+        let bytes: Vec<u8> = (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect();
+        BigUint::from_bytes_le(&bytes)
+    };
+    // RFC 7748, section 5.2
+    let mut k = hex_le("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4").to_bytes_le();
+    k[0] &= 248;
+    k[31] &= 127;
+    k[31] |= 64;
+    let u = hex_le("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c");
+    let u = Felem::from(u % (BigUint::from(1u32) << 255));
+    let out = ladder::ladder(&BigUint::from_bytes_le(&k), 255, u, Felem::from_literal(121665));
+    let expected = hex_le("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552");
+    assert_eq!(BigUint::from(out), expected);
+    let (mut a, mut b) = (Felem::from_literal(1), Felem::from_literal(2));
+    ladder::cswap(Felem::from_literal(1), &mut a, &mut b);
+    assert_eq!((a, b), (Felem::from_literal(2), Felem::from_literal(1)));
+}
+
+define_refined_modular_integer!(
+    Ed25519Scalar,
+    BigBounded,
+    BigBounded::pow2(252) + BigBounded::from_hex("14def9dea2f79cd65812631a5cf5d3ed")
+);
+
+#[test]
+fn rfc_encodings() {
+    let bytes = |s: &str| -> [u8; 32] {
+        let v: Vec<u8> = (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect();
+        std::convert::TryInto::try_into(v).unwrap()
+    };
+    // RFC 7748, section 5.2
+    let k = encoding::decode_scalar_25519(&bytes("4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d"));
+    let u: Felem = encoding::decode_u_coordinate_25519(&bytes("e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493"));
+    let out = ladder::ladder(&k, 255, u, Felem::from_literal(121665));
+    assert_eq!(
+        encoding::encode_u_coordinate::<Felem, 32>(out),
+        bytes("95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957")
+    );
+    // non-canonical u-coordinates are reduced
+    let mut p_plus_one = [0xffu8; 32];
+    p_plus_one[0] = 0xee;
+    p_plus_one[31] = 0xff;
+    assert_eq!(encoding::decode_u_coordinate_25519::<Felem>(&p_plus_one), Felem::from_literal(1));
+    assert!(encoding::decode_point_25519::<Felem>(&p_plus_one).is_none());
+    // RFC 8032 point encoding: sign of x in the top bit
+    let enc = encoding::encode_point_25519(Felem::from_literal(4), true);
+    assert_eq!(enc[31], 0x80);
+    assert_eq!(encoding::decode_point_25519::<Felem>(&enc), Some((Felem::from_literal(4), true)));
+    let l = encoding::encode_scalar_25519(Ed25519Scalar::from_literal(0) - Ed25519Scalar::from_literal(1));
+    assert!(encoding::decode_scalar_8032::<Ed25519Scalar>(&l).is_some());
+    let mut l_plus = l;
+    l_plus[0] += 1;
+    assert!(encoding::decode_scalar_8032::<Ed25519Scalar>(&l_plus).is_none());
+}
+
+#[cfg(feature = "generic-array")]
+#[test]
+fn generic_arrays() {
+    use generic_array::GenericArray;
+    use typenum::U32;
+    let x = BigBounded::from_literal(0x0102);
+    let a: GenericArray<u8, U32> = x.into();
+    assert_eq!(a[30..], [1, 2]);
+    assert_eq!(BigBounded::from(a), x);
+    let y = LittleNat::from_literal(0x0102);
+    assert_eq!(y.to_generic_array()[..2], [2, 1]);
+    let f = Felem::from_literal(7);
+    let a: GenericArray<u8, U32> = f.into();
+    assert_eq!(Felem::from_generic_array(&a), f);
+}
+
+#[test]
+fn size_constants() {
+    use crate::traits::{CheckedInteger, ModularInteger};
+    assert_eq!((BigBounded::BITS, BigBounded::BYTES), (256, 32));
+    assert_eq!(<LittleNat as CheckedInteger>::BYTES, 32);
+    assert_eq!((Felem::BITS, Felem::BYTES), (256, 32));
+    assert_eq!(<Felem as ModularInteger>::BYTES, 32);
+    assert_eq!(Felem::modulus(), BigBounded::pow2(255) - BigBounded::from_literal(19));
+    assert_eq!(<Felem as ModularInteger>::modulus(), BigUint::from(Felem::modulus()));
+}
+
+#[test]
+fn reduce_into_other_modulus() {
+    let x = Felem::from_literal(300);
+    let y: SmallModular = x.reduce_into();
+    assert_eq!(y, SmallModular::from_literal(45));
+    let z = (Felem::from_literal(0) - Felem::from_literal(1)).reduce_into::<SmallModular>();
+    let expected = ((BigUint::from(1u32) << 255) - 20u32) % 255u32;
+    assert_eq!(BigUint::from(z), expected);
+}
+
+#[test]
+fn pow_mod_big_exponent() {
+    let x = Felem::from_literal(3);
+    let e = BigUint::from(u128::MAX) * 7u32 + 5u32;
+    let m = BigUint::from(Felem::modulus());
+    assert_eq!(BigUint::from(x.pow_mod_big(&e)), BigUint::from(3u32).modpow(&e, &m));
+    // Fermat: x^(p-1) = 1.
+    assert_eq!(x.pow_mod_big(&(m - 1u32)), Felem::from_literal(1));
+    assert_eq!(x.pow_mod_big(&BigUint::from(0u32)), Felem::from_literal(1));
+}
+
+#[test]
+fn nat_literals() {
+    assert_eq!(nat!(BigBounded, 1_000_000), BigBounded::from_literal(1_000_000));
+    assert_eq!(nat!(BigBounded, 0b1011), BigBounded::from_literal(11));
+    assert_eq!(nat!(LittleNat, 0o777), LittleNat::from_literal(511));
+    assert_eq!(
+        nat!(Felem, 0x7fff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffec),
+        Felem::from_literal(0) - Felem::from_literal(1)
+    );
+    assert_eq!(crate::__literal_bits("0x00ff"), 8);
+    assert_eq!(crate::__literal_bits("0b0"), 0);
+    assert_eq!(crate::__literal_bits("340282366920938463463374607431768211455"), 128);
+    assert_eq!(nat!(Gf257, 256), Gf257::from_literal(256));
+    assert_eq!(Gf257::__LITERAL_BOUND, Some(257));
+    assert_eq!(Felem::__LITERAL_BOUND, None);
+    assert!(crate::__literal_below("0x100", Some(257)));
+    assert!(!crate::__literal_below("257", Some(257)));
+    assert!(!crate::__literal_below("0x1_0000_0000_0000_0000_0000_0000_0000_0000", Some(257)));
+    let too_big = std::panic::catch_unwind(|| {
+        nat!(Felem, 0x7fff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffed)
+    });
+    assert!(too_big.is_err());
+}
+
+#[test]
+fn division_by_fixed_divisor() {
+    let ten = BigBounded::from_literal(10).divisor_context();
+    let mut x = Felem::modulus();
+    while x > BigBounded::from_literal(0) {
+        let (q, r) = ten.div_rem(x);
+        assert_eq!((q, r), (x / BigBounded::from_literal(10), x % BigBounded::from_literal(10)));
+        x = q;
+    }
+    let max = BigBounded::from(BigBounded::max());
+    let d = BigBounded::from_literal(3) * BigBounded::pow2(200) + BigBounded::from_literal(7);
+    let ctx = d.divisor_context();
+    assert_eq!(ctx.divisor(), d);
+    assert_eq!(ctx.div(max), max / d);
+    assert_eq!(ctx.rem(max), max % d);
+}
+
+define_refined_modular_integer!(Gf257Tables, LittleNat, LittleNat::from_literal(257), tables);
+define_refined_modular_integer!(Z1000Tables, BigBounded, BigBounded::from_literal(1000), tables);
+
+#[test]
+fn table_backend() {
+    for a in 0..257u128 {
+        for b in (0..257u128).step_by(7) {
+            let (x, y) = (Gf257Tables::from_literal(a), Gf257Tables::from_literal(b));
+            let (u, v) = (Gf257::from_literal(a), Gf257::from_literal(b));
+            assert_eq!(BigUint::from(x + y), BigUint::from(u + v));
+            assert_eq!(BigUint::from(x - y), BigUint::from(u - v));
+            assert_eq!(BigUint::from(x * y), BigUint::from(u * v));
+        }
+        let (x, u) = (Gf257Tables::from_literal(a), Gf257::from_literal(a));
+        assert_eq!(BigUint::from(x.inv()), BigUint::from(u.inv()));
+    }
+    let x = Z1000Tables::from_literal(999) * Z1000Tables::from_literal(998);
+    assert_eq!(x, Z1000Tables::from_literal(2));
+    let y = Z1000Tables::from_literal(3) - Z1000Tables::from_literal(5);
+    assert_eq!(y, Z1000Tables::from_literal(998));
+    assert_eq!(Gf257Tables::tables().modulus(), 257);
+}
+
+define_refined_modular_integer!(
+    TooBigTables,
+    BigBounded,
+    BigBounded::pow2(16) + BigBounded::from_literal(1),
+    tables
+);
+
+#[test]
+#[should_panic(expected = "is not between 2 and 2^16 for operation tables")]
+fn table_backend_modulus_too_big() {
+    let _ = TooBigTables::from_literal(2) * TooBigTables::from_literal(3);
+}
+
+#[test]
+fn small_modulus_inverse_table() {
+    let m = BigUint::from(257u32);
+    for a in 1..257u128 {
+        let x = Gf257::from_literal(a);
+        assert_eq!(x * x.inv(), Gf257::from_literal(1));
+        assert_eq!(BigUint::from(x.inv()), BigUint::from(a).modpow(&BigUint::from(255u32), &m));
+    }
+    assert_eq!(Gf257::from_literal(0).inv(), Gf257::from_literal(0));
+    assert_eq!(crate::tables::inverse_table(&BigUint::from(7u32)), [0, 1, 4, 5, 2, 3, 6]);
+    let big = Felem::from_literal(2);
+    assert_eq!(big * big.inv(), Felem::from_literal(1));
+}
+
+#[test]
+fn deferred_reduction_accumulator() {
+    let xs: Vec<Felem> =
+        (1..200u128).map(|i| Felem::from_literal(0) - Felem::from_literal(i)).collect();
+    let mut acc = Accumulator::<Felem>::with_headroom(8);
+    let mut expected = Felem::from_literal(0);
+    for (i, x) in xs.iter().enumerate() {
+        acc.add_product(*x, xs[xs.len() - 1 - i]);
+        acc.add(*x);
+        expected = expected + *x * xs[xs.len() - 1 - i] + *x;
+        assert_eq!(acc.value(), expected);
+    }
+    assert_eq!(acc.finish(), expected);
+    assert_eq!(Accumulator::<Gf257>::default().finish(), Gf257::from_literal(0));
+}
+
+#[test]
+fn fused_multiply_add() {
+    let m1 = Felem::from_literal(0) - Felem::from_literal(1);
+    assert_eq!(m1.mul_add(m1, m1), m1 * m1 + m1);
+    let a = Poly1305Field::from_literal(1 << 100);
+    let (b, c) = (Poly1305Field::from_literal(3), Poly1305Field::from_literal(9));
+    assert_eq!(a.mul_add(b, c), a * b + c);
+    let (x, y) = (Gf257Tables::from_literal(200), Gf257Tables::from_literal(100));
+    assert_eq!(x.mul_add(y, x), Gf257Tables::from_literal((200 * 100 + 200) % 257));
+}
+
+#[test]
+fn squaring() {
+    let x = BigBounded::pow2(127) + BigBounded::from_literal(3);
+    assert_eq!(x.square(), x * x);
+    let overflow = std::panic::catch_unwind(|| BigBounded::pow2(128).square());
+    assert!(overflow.is_err());
+    let y = Felem::from_literal(0) - Felem::from_literal(2);
+    assert_eq!(y.square(), Felem::from_literal(4));
+    assert_eq!(<Felem as ModularInteger>::square(y), y * y);
+    let z = Gf257Tables::from_literal(256);
+    assert_eq!(z.square(), Gf257Tables::from_literal(1));
+}
+
+#[test]
+fn small_multiples() {
+    let y = Felem::from_literal(0) - Felem::from_literal(5);
+    assert_eq!(y.double(), y * Felem::from_literal(2));
+    assert_eq!(y.triple(), y * Felem::from_literal(3));
+    for k in [0u64, 1, 2, 7, 8, 1000, u64::MAX] {
+        assert_eq!(y.mul_small(k), y * Felem::from(k));
+    }
+    assert_eq!(Gf257Tables::from_literal(200).mul_small(3), Gf257Tables::from_literal(86));
+}
+
+#[test]
+fn signed_exponents() {
+    let g = Felem::from_literal(5);
+    assert_eq!(g.pow_mod_signed(-3) * g.pow(3), Felem::from_literal(1));
+    assert_eq!(g.pow_mod_signed(-1), g.inv());
+    assert_eq!(g.pow_mod_signed(4), g.pow(4));
+    assert_eq!(g.pow_mod_signed(i128::MIN), g.inv().pow(1 << 127));
+    let zero = std::panic::catch_unwind(|| Felem::from_literal(0).pow_mod_signed(-2));
+    assert!(zero.is_err());
+}
+
+define_abstract_integer_checked!(ProtoNat, 16);
+
+#[test]
+fn overflow_promotion() {
+    crate::promotion::take_promotions();
+    let x = ProtoNat::from_literal(300).promoting();
+    let y = x.clone() + x.clone();
+    assert_eq!(y, Promoting::Bounded(ProtoNat::from_literal(600)));
+    let z = y.clone() * y.clone();
+    assert_eq!(z, Promoting::Unbounded(BigUint::from(360_000u32)));
+    let w = z.clone() - z;
+    assert!(!w.is_bounded());
+    assert_eq!(w.value(), BigUint::from(0u32));
+    assert_eq!(format!("{}", w), "0 (unbounded)");
+    let promotions = crate::promotion::take_promotions();
+    assert_eq!(promotions.len(), 1);
+    assert_eq!(promotions[0].type_name, "ProtoNat");
+    assert_eq!(promotions[0].operation, "*");
+    assert_eq!(promotions[0].operands, vec![BigUint::from(600u32), BigUint::from(600u32)]);
+}
+
+#[test]
+fn unbounded_naturals() {
+    fn sum_of_squares<T: AbstractInteger>(n: u128) -> T {
+        (1..=n).fold(T::from_literal(0), |acc, i| acc + T::from_literal(i) * T::from_literal(i))
+    }
+    let exact: UnboundedNat = sum_of_squares(100);
+    assert_eq!(exact, UnboundedNat::from_literal(338_350));
+    let pinned: ProtoNat = sum_of_squares(30);
+    assert_eq!(BigUint::from(pinned), BigUint::from(9455u32));
+    assert_eq!(<UnboundedNat as AbstractInteger>::bound(), None);
+    assert_eq!(<ProtoNat as AbstractInteger>::bound(), Some(BigUint::from(65535u32)));
+    let big = UnboundedNat::from(BigUint::from(1u32) << 1000);
+    assert_eq!(big.clone() / big.clone(), UnboundedNat::from_literal(1));
+    assert_eq!(big.clone() % UnboundedNat::from_literal(3), UnboundedNat::from_literal(1));
+    assert!(std::panic::catch_unwind(|| UnboundedNat::from_literal(1) - big).is_err());
+}
+
+#[test]
+fn exact_fractions() {
+    let r = |n, d| Ratio::new(ProtoNat::from_literal(n), ProtoNat::from_literal(d));
+    let fee = r(3, 1000);
+    assert_eq!(fee, r(6, 2000));
+    assert_eq!(*fee.denom(), ProtoNat::from_literal(1000));
+    assert_eq!(fee.clone() + r(1, 1000), r(1, 250));
+    assert_eq!(r(1, 2) - r(1, 3), r(1, 6));
+    assert_eq!(r(2, 3) * r(3, 4), r(1, 2));
+    assert_eq!(r(1, 2) / r(1, 4), Ratio::from_integer(ProtoNat::from_literal(2)));
+    assert!(r(1, 3) < r(1, 2));
+    assert_eq!(r(7, 2).floor(), ProtoNat::from_literal(3));
+    assert_eq!(r(7, 2).ceil(), ProtoNat::from_literal(4));
+    assert_eq!(format!("{}", r(0, 5)), "0/1");
+    // The intermediate denominators overflow ProtoNat, the reduced results do not.
+    assert_eq!(r(1000, 999) * r(999, 1000), r(1, 1));
+    assert!(std::panic::catch_unwind(|| r(1, 3) - r(1, 2)).is_err());
+    let u = |n, d| Ratio::new(UnboundedNat::from_literal(n), UnboundedNat::from_literal(d));
+    assert_eq!(u(10, 4).recip(), u(2, 5));
+}
+
+define_abstract_integer_checked!(Nat128, 128);
+define_fixed_point!(Rate, Nat128, 64);
+
+#[test]
+fn fixed_point() {
+    let three_quarters = Rate::from_integer(3) / Rate::from_integer(4);
+    assert_eq!(format!("{}", three_quarters), "0.75");
+    assert_eq!(three_quarters.raw(), Nat128::from_literal(3 << 62));
+    let x = Rate::from_integer(10) * three_quarters + Rate::from_integer(1);
+    assert_eq!(format!("{:?}", x), "8.5");
+    assert_eq!(x.floor(), Nat128::from_literal(8));
+    let third = Rate::from_integer(1) / Rate::from_integer(3);
+    assert_eq!(third * Rate::from_integer(3), Rate::from_raw(Nat128::from_literal((1 << 64) - 1)));
+    assert!(std::panic::catch_unwind(|| Rate::from_integer(1 << 64)).is_err());
+    assert!(std::panic::catch_unwind(|| Rate::from_integer(1) / Rate::default()).is_err());
+    let big = Rate::from_integer(1 << 40);
+    assert!(std::panic::catch_unwind(|| big * big).is_err());
+}
+
+#[test]
+fn float_conversions() {
+    use crate::diagnostics::FromFloatError;
+    assert_eq!(ProtoNat::from_literal(1234).to_f64_lossy(), 1234.0);
+    assert_eq!(BigBounded::pow2(200).to_f64_lossy(), 2f64.powi(200));
+    assert_eq!(Felem::from_literal(7).to_f64_lossy(), 7.0);
+    assert_eq!(ProtoNat::try_from_f64(65535.0), Ok(ProtoNat::from_literal(65535)));
+    assert_eq!(ProtoNat::try_from_f64(-1.0), Err(FromFloatError::Negative));
+    assert_eq!(ProtoNat::try_from_f64(f64::NAN), Err(FromFloatError::NotFinite));
+    assert_eq!(ProtoNat::try_from_f64(1.5), Err(FromFloatError::NotInteger));
+    assert!(matches!(ProtoNat::try_from_f64(65536.0), Err(FromFloatError::OutOfRange(_))));
+    assert_eq!(Gf257::try_from_f64(256.0), Ok(Gf257::from_literal(256)));
+    assert!(matches!(Gf257::try_from_f64(257.0), Err(FromFloatError::OutOfRange(_))));
+}
+
+#[cfg(feature = "ssz")]
+#[test]
+fn ssz_encoding() {
+    use ssz::{Decode, DecodeError, Encode};
+    let x = BigBounded::from_literal(0x0102);
+    let mut expected = vec![0u8; 32];
+    expected[..2].copy_from_slice(&[2, 1]);
+    assert_eq!(x.as_ssz_bytes(), expected);
+    assert_eq!(BigBounded::from_ssz_bytes(&expected), Ok(x));
+    assert_eq!(LittleNat::from_literal(0x0102).as_ssz_bytes(), expected);
+    assert_eq!(<ProtoNat as Encode>::ssz_fixed_len(), 2);
+    assert_eq!(
+        ProtoNat::from_ssz_bytes(&[1]),
+        Err(DecodeError::InvalidByteLength { len: 1, expected: 2 })
+    );
+    assert!(LittleNat::from_ssz_bytes(&[0xff; 32]).is_err());
+}
+
+#[cfg(feature = "rlp")]
+#[test]
+fn rlp_encoding() {
+    use rlp::DecoderError;
+    assert_eq!(rlp::encode(&BigBounded::from_literal(0)).to_vec(), [0x80]);
+    assert_eq!(rlp::encode(&BigBounded::from_literal(0x7f)).to_vec(), [0x7f]);
+    assert_eq!(rlp::encode(&BigBounded::from_literal(0x0400)).to_vec(), [0x82, 0x04, 0x00]);
+    let x = rlp::decode::<BigBounded>(&[0x82, 0x04, 0x00]);
+    assert_eq!(x, Ok(BigBounded::from_literal(0x0400)));
+    let leading_zero = rlp::decode::<ProtoNat>(&[0x82, 0x00, 0x04]);
+    assert_eq!(leading_zero, Err(DecoderError::RlpInvalidIndirection));
+    assert!(rlp::decode::<ProtoNat>(&[0x83, 0x01, 0x00, 0x00]).is_err());
+    let x = Felem::from_literal(0) - Felem::from_literal(1);
+    assert_eq!(rlp::decode::<Felem>(&rlp::encode(&x)), Ok(x));
+    assert!(rlp::decode::<Gf257>(&[0x82, 0x01, 0x01]).is_err());
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+fn canonical_cbor() {
+    use crate::cbor::CborError;
+    assert_eq!(BigBounded::from_literal(23).to_cbor(), [0x17]);
+    assert_eq!(BigBounded::from_literal(500).to_cbor(), [0x19, 0x01, 0xf4]);
+    let big = BigBounded::pow2(64);
+    let encoded = [0xc2, 0x49, 1, 0, 0, 0, 0, 0, 0, 0, 0];
+    assert_eq!(big.to_cbor(), encoded);
+    assert_eq!(BigBounded::from_cbor(&encoded), Ok(big));
+    assert_eq!(BigBounded::from_cbor(&[0x18, 0x17]), Err(CborError::NonCanonical));
+    assert_eq!(BigBounded::from_cbor(&[0xc2, 0x41, 0x05]), Err(CborError::NonCanonical));
+    assert_eq!(BigBounded::from_cbor(&[0x17, 0x00]), Err(CborError::TrailingBytes));
+    assert_eq!(BigBounded::from_cbor(&[0x20]), Err(CborError::UnexpectedType));
+    assert_eq!(BigBounded::from_cbor(&[0x19, 0x01]), Err(CborError::Truncated));
+    assert!(matches!(ProtoNat::from_cbor(&[0x1a, 0, 1, 0, 0]), Err(CborError::OutOfRange(_))));
+    let x = Felem::from_literal(0) - Felem::from_literal(1);
+    assert_eq!(Felem::from_cbor(&x.to_cbor()), Ok(x));
+}
+
+#[cfg(feature = "prost")]
+#[test]
+fn protobuf_bytes_fields() {
+    use prost::bytes::Bytes;
+    use std::convert::TryFrom;
+    let field: Vec<u8> = BigBounded::from_literal(0x0102).into();
+    assert_eq!(field, [1, 2]);
+    assert_eq!(BigBounded::try_from(field), Ok(BigBounded::from_literal(0x0102)));
+    let field: Bytes = Gf257::from_literal(256).into();
+    assert_eq!(Gf257::try_from(field), Ok(Gf257::from_literal(256)));
+    assert_eq!(ProtoNat::try_from(&[0u8, 0, 0xff, 0xff][..]), Ok(ProtoNat::from_literal(65535)));
+    assert!(ProtoNat::try_from(vec![1u8, 0, 0]).is_err());
+    assert!(Gf257::try_from(Bytes::from_static(&[1, 1])).is_err());
+}
+
+#[test]
+fn alternate_debug() {
+    let x = ProtoNat::from_literal(300);
+    assert_eq!(format!("{:?}", x), "300");
+    assert_eq!(
+        format!("{:#?}", x),
+        "ProtoNat {\n    bits: 16,\n    hex: 0x12c,\n    decimal: 300,\n}"
+    );
+    assert_eq!(
+        format!("{:#?}", Gf257::from_literal(16)),
+        "Gf257 {\n    modulus: 0x101,\n    hex: 0x10,\n    decimal: 16,\n}"
+    );
+    assert_eq!(
+        format!("{:#?}", SecretKey::from_literal(5)),
+        "SecretKey {\n    bits: 256,\n    value: <redacted>,\n}"
+    );
+    assert_eq!(
+        format!("{:#?}", SecretScalar::from_literal(5)),
+        "SecretScalar {\n    modulus: 0x3f1,\n    value: <redacted>,\n}"
+    );
+}
+
+#[cfg(feature = "registry")]
+#[test]
+fn type_registry() {
+    let find = |name| crate::registry::types().find(|t| t.name == name).unwrap();
+    let proto = find("ProtoNat");
+    assert_eq!((proto.bits, proto.base, proto.modulus()), (16, None, None));
+    assert_eq!(proto.module_path, "abstract_integers::tests");
+    let gf = find("Gf257");
+    assert_eq!((gf.bits, gf.base), (256, Some("BigBounded")));
+    assert_eq!(gf.modulus(), Some(BigUint::from(257u32)));
+    assert!(crate::registry::types().any(|t| t.name == "SizeNatFieldExample"));
+}
+
+define_machine_integer!(U32spec, 32);
+define_machine_integer!(U7spec, 7);
+define_machine_integer!(U130spec, 130);
+
+#[test]
+fn machine_integers() {
+    let w = |x: u32| U32spec::from(x);
+    for &(a, b) in &[(0xffff_ffffu32, 2u32), (0x1234_5678, 0x9abc_def0), (0, 1), (7, 0xdead_beef)] {
+        assert_eq!(w(a) + w(b), w(a.wrapping_add(b)));
+        assert_eq!(w(a) - w(b), w(a.wrapping_sub(b)));
+        assert_eq!(w(a) * w(b), w(a.wrapping_mul(b)));
+        assert_eq!(w(a) ^ w(b), w(a ^ b));
+        assert_eq!(w(a) & w(b), w(a & b));
+        assert_eq!(w(a) | w(b), w(a | b));
+        assert_eq!(!w(a), w(!a));
+        for n in [0, 1, 7, 16, 31] {
+            assert_eq!(w(a).rotate_left(n), w(a.rotate_left(n)));
+            assert_eq!(w(a).rotate_right(n), w(a.rotate_right(n)));
+            assert_eq!(w(a) << n as usize, w(a << n));
+            assert_eq!(w(a) >> n as usize, w(a >> n));
+        }
+    }
+    assert_eq!(U32spec::from(0x1_0000_0001u64), w(1));
+    assert_eq!(U7spec::from_literal(127) + U7spec::from_literal(1), U7spec::from_literal(0));
+    assert_eq!(U7spec::from_literal(1).rotate_right(1), U7spec::from_literal(64));
+    assert_eq!(!U7spec::from_literal(0), U7spec::from_literal(127));
+    assert_eq!((U32spec::BITS, U32spec::BYTES), (32, 4));
+    assert!(std::panic::catch_unwind(|| w(1) << 32).is_err());
+
+    let m = BigUint::from(1u32) << 130;
+    let big = |x: &BigUint| U130spec::from(x.clone());
+    let a = (BigUint::from(0xdead_beef_1234_5678u64) << 60) + 0xffff_ffff_ffff_ffffu64;
+    let b = (BigUint::from(3u32) << 128) + 0x1_0000_0001u64;
+    assert_eq!(BigUint::from(big(&a) + big(&b)), (&a + &b) % &m);
+    assert_eq!(BigUint::from(big(&b) - big(&a)), (&m + &b - &a) % &m);
+    assert_eq!(BigUint::from(big(&a) * big(&b)), (&a * &b) % &m);
+    assert_eq!(BigUint::from(!big(&a)), &m - 1u32 - &a);
+    for n in [1, 63, 64, 65, 129] {
+        assert_eq!(BigUint::from(big(&a) << n), (&a << n) % &m);
+        assert_eq!(BigUint::from(big(&a) >> n), &a >> n);
+        let rotated = ((&a << n) | (&a >> (130 - n))) % &m;
+        assert_eq!(BigUint::from(big(&a).rotate_left(n as u32)), rotated);
+    }
+}
+
+define_machine_integer!(SecretU8, 8, secret);
+
+#[test]
+fn secret_machine_integers() {
+    let s = |x: u8| SecretU8::from(x);
+    let values = [0u8, 1, 7, 128, 200, 255];
+    for &a in &values {
+        for &b in &values {
+            let bm = |c: bool| if c { 0xffu32 } else { 0 };
+            assert_eq!(s(a).eq_mask(s(b)).declassify(), BigUint::from(bm(a == b)));
+            assert_eq!(s(a).ne_mask(s(b)).declassify(), BigUint::from(bm(a != b)));
+            assert_eq!(s(a).lt_mask(s(b)).declassify(), BigUint::from(bm(a < b)));
+            assert_eq!(s(a).le_mask(s(b)).declassify(), BigUint::from(bm(a <= b)));
+            assert_eq!(s(a).gt_mask(s(b)).declassify(), BigUint::from(bm(a > b)));
+            assert_eq!(s(a).ge_mask(s(b)).declassify(), BigUint::from(bm(a >= b)));
+        }
+    }
+    let m = s(3).lt_mask(s(5));
+    assert_eq!(m.select(s(10), s(20)).declassify(), BigUint::from(10u32));
+    assert_eq!((!m).select(s(10), s(20)).declassify(), BigUint::from(20u32));
+    assert_eq!((s(250) + s(10)).declassify(), BigUint::from(4u32));
+    assert_eq!(format!("{}", s(42)), "<redacted 8-bit secret>");
+    // The call to `check` is ambiguous, hence rejected, when `T` converts into `BigUint`.
+    trait AmbiguousIfInto<A> {
+        fn check() {}
+    }
+    impl<T> AmbiguousIfInto<()> for T {}
+    impl<T: Into<BigUint>> AmbiguousIfInto<u8> for T {}
+    <SecretU8 as AmbiguousIfInto<_>>::check();
+}
+
+define_machine_integer!(PublicU8, 8);
+define_abstract_integer_checked!(SecretBlock, 32, secret);
+
+#[test]
+fn byte_sequences() {
+    let msg = ByteSeq::<PublicU8>::from_public_slice(b"abcdefghij");
+    assert_eq!(msg.len(), 10);
+    assert_eq!(msg[1], PublicU8::from(b'b'));
+    assert_eq!(msg.slice(2, 3).declassify(), b"cde");
+    assert_eq!(msg.num_chunks(4), 3);
+    let chunks: Vec<_> = msg.chunks(4).map(|c| c.declassify()).collect();
+    assert_eq!(chunks, [&b"abcd"[..], b"efgh", b"ij"]);
+    let word: Nat128 = msg.slice(0, 4).to_integer_be();
+    assert_eq!(word, Nat128::from_literal(0x61626364));
+    let le: Nat128 = msg.slice(0, 4).to_integer_le();
+    assert_eq!(le, Nat128::from_literal(0x64636261));
+    let seq = ByteSeq::<PublicU8>::from_integer_le(ProtoNat::from_literal(0x0102));
+    assert_eq!(seq.concat(&msg.slice(0, 1)).declassify(), [2, 1, b'a']);
+    let secret = ByteSeq::<SecretU8>::from_integer_be(ProtoNat::from_literal(0x0102));
+    assert_eq!(secret.declassify(), [1, 2]);
+    let block: SecretBlock = secret.concat(&ByteSeq::new(2)).to_integer_be();
+    assert_eq!(BigUint::from(block), BigUint::from(0x01020000u32));
+}
+
+#[test]
+fn word_decomposition() {
+    let x = Nat128::from_hex("0123456789abcdeffedcba9876543210");
+    assert_eq!(x.to_u32s_be(), [0x01234567, 0x89abcdef, 0xfedcba98, 0x76543210]);
+    assert_eq!(x.to_u32s_le(), [0x76543210, 0xfedcba98, 0x89abcdef, 0x01234567]);
+    assert_eq!(x.to_u64s_be(), [0x0123456789abcdef, 0xfedcba9876543210]);
+    assert_eq!(x.to_u64s_le(), [0xfedcba9876543210, 0x0123456789abcdef]);
+    assert_eq!(Nat128::from_u32s_be(x.to_u32s_be()), x);
+    assert_eq!(Nat128::from_u32s_le(x.to_u32s_le()), x);
+    assert_eq!(Nat128::from_u64s_be(x.to_u64s_be()), x);
+    assert_eq!(Nat128::from_u64s_le(x.to_u64s_le()), x);
+    let small = SizeNatExample::from_literal(0x1_0000_0002);
+    assert_eq!(SizeNatExample::WORDS32, 2);
+    assert_eq!(small.to_u32s_be(), [1, 2]);
+    assert_eq!(SizeNatExample::from_u32s_le([2, 1]), small);
+    assert_eq!(LittleNat::from_u32s_be(LittleNat::from_literal(5).to_u32s_be()).to_u32s_le()[0], 5);
+}
+
+define_abstract_integer_checked!(Params, 64; constants { GENERATOR = 5, COFACTOR = 0x8 });
+define_refined_modular_integer!(
+    Gf257Consts,
+    ProtoNat,
+    ProtoNat::from_literal(257);
+    constants { TWO = 2, GENERATOR = 3 }
+);
+
+#[test]
+fn named_constants() {
+    assert_eq!(Params::GENERATOR, Params::from_literal(5));
+    assert_eq!(Params::COFACTOR * Params::GENERATOR, Params::from_literal(40));
+    assert_eq!(Gf257Consts::GENERATOR.pow(256), Gf257Consts::from_literal(1));
+    assert_eq!(Gf257Consts::TWO + Gf257Consts::from_literal(255), Gf257Consts::from_literal(0));
+    const LITTLE: LittleNat = LittleNat::__from_u128_const(0x0102);
+    assert_eq!(LITTLE, LittleNat::from_literal(0x0102));
+}
+
+#[test]
+fn comparisons_with_base() {
+    let x = Gf257Consts::from_literal(256);
+    assert!(x < ProtoNat::from_literal(257));
+    assert!(x == ProtoNat::from_literal(256));
+    assert!(x != ProtoNat::from_literal(0));
+    assert!(x > ProtoNat::from_literal(3));
+    assert!(Gf257Consts::from(ProtoNat::from_literal(300)) == ProtoNat::from_literal(43));
+}
+
+#[test]
+fn slice_reductions() {
+    let xs: Vec<Gf257> = (0..300u32).map(|i| Gf257::from_literal((i * 37 % 257) as u128)).collect();
+    let sum = xs.iter().fold(Gf257::from_literal(0), |acc, x| acc + *x);
+    assert_eq!(Gf257::sum_mod(&xs), sum);
+    let nonzero: Vec<Gf257> = xs.iter().copied().filter(|x| *x != Gf257::from_literal(0)).collect();
+    let product = nonzero.iter().fold(Gf257::from_literal(1), |acc, x| acc * *x);
+    assert_eq!(Gf257::product_mod(&nonzero), product);
+    assert_eq!(Gf257::product_mod(&xs), Gf257::from_literal(0));
+    assert_eq!(Gf257::sum_mod(&[]), Gf257::from_literal(0));
+    assert_eq!(Gf257::product_mod(&[]), Gf257::from_literal(1));
+}
+
+#[test]
+fn jacobi_symbols() {
+    // (a / 15) for a = 0..15, from the standard table.
+    let expected = [0, 1, 1, 0, 1, 0, 0, -1, 1, 0, 0, -1, 0, -1, -1];
+    for (a, j) in expected.iter().enumerate() {
+        let a = SizeNatExample::from_literal(a as u128);
+        assert_eq!(a.jacobi(SizeNatExample::from_literal(15)), *j);
+    }
+    assert_eq!(SizeNatExample::from_literal(1001).jacobi(SizeNatExample::from_literal(9907)), -1);
+    assert_eq!(SizeNatExample::from_literal(19).jacobi(SizeNatExample::from_literal(45)), 1);
+    let squares = (1..257u32).filter(|a| Gf257::from_literal(*a as u128).jacobi() == 1).count();
+    assert_eq!(squares, 128);
+    assert_eq!(Gf257::from_literal(9).jacobi(), 1);
+    assert_eq!(Gf257::from_literal(0).jacobi(), 0);
+}
+
+#[test]
+fn primality() {
+    let sieve_limit = 3000u32;
+    let mut is_prime = vec![true; sieve_limit as usize];
+    is_prime[0] = false;
+    is_prime[1] = false;
+    for i in 2..sieve_limit as usize {
+        if is_prime[i] {
+            (2 * i..sieve_limit as usize).step_by(i).for_each(|j| is_prime[j] = false);
+        }
+    }
+    for n in 0..sieve_limit {
+        let x = SizeNatExample::from_literal(n as u128);
+        assert_eq!(x.is_prime_bpsw(), is_prime[n as usize], "{}", n);
+        assert_eq!(x.is_probable_prime_fixed_bases(5), is_prime[n as usize], "{}", n);
+    }
+    // Strong pseudoprimes to base 2, and a Carmichael number.
+    for n in [2047u128, 3277, 4033, 4681, 8321, 561, 3215031751] {
+        assert!(!SizeNatExample::from_literal(n).is_prime_bpsw(), "{}", n);
+    }
+    assert!(!SizeNatExample::from_literal(3215031751).is_probable_prime_fixed_bases(5));
+    assert!(SizeNatExample::from_literal(3215031751).is_probable_prime_fixed_bases(4));
+    let p = Nat128::from_literal((1 << 127) - 1);
+    assert!(p.is_prime_bpsw());
+    assert!(p.is_probable_prime_fixed_bases(10));
+    assert!(!Nat128::from_literal((1 << 127) + 1).is_prime_bpsw());
+    assert!(std::panic::catch_unwind(|| p.is_probable_prime_fixed_bases(26)).is_err());
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn random_primes() {
+    use rand::SeedableRng;
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(42);
+    for bits in [2, 3, 8, 61, 128] {
+        let p = Nat128::random_prime(bits, &mut rng);
+        assert_eq!(BigUint::from(p).bits(), bits);
+        assert!(p.is_probable_prime(20, &mut rng));
+    }
+    let n = SizeNatExample::from_literal(3215031751);
+    assert!(!n.is_probable_prime(20, &mut rng));
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(7);
+    assert!(std::panic::catch_unwind(move || ProtoNat::random_prime(17, &mut rng)).is_err());
+}
+
+#[test]
+fn pollard_rho_factorization() {
+    let f = |n: u128| -> Vec<(u128, u32)> {
+        Nat128::from_literal(n)
+            .factor()
+            .into_iter()
+            .map(|(p, e)| (num::ToPrimitive::to_u128(&BigUint::from(p)).unwrap(), e))
+            .collect()
+    };
+    assert_eq!(f(1), []);
+    assert_eq!(f(97), [(97, 1)]);
+    assert_eq!(f(360), [(2, 3), (3, 2), (5, 1)]);
+    assert_eq!(f(10403 * 10403 * 101), [(101, 3), (103, 2)]);
+    assert_eq!(f(600851475143), [(71, 1), (839, 1), (1471, 1), (6857, 1)]);
+    let (p, q) = (65537, (1 << 61) - 1);
+    assert_eq!(f(p * q), [(p, 1), (q, 1)]);
+    assert_eq!(f(1000003 * 1000003), [(1000003, 2)]);
+}
+
+#[test]
+fn discrete_logarithms() {
+    let g = Gf257::from_literal(3);
+    for x in [0u64, 1, 2, 100, 255] {
+        assert_eq!(g.discrete_log(g.pow(x as u128), 256), Some(x));
+    }
+    assert_eq!(g.discrete_log(g.pow(200), 100), None);
+    // Bounds around perfect squares, where the number of steps is the tightest.
+    for bound in [1u64, 2, 3, 4, 5, 15, 16, 17] {
+        assert_eq!(g.discrete_log(g.pow(bound as u128 - 1), bound), Some(bound - 1));
+    }
+    assert_eq!(g.discrete_log(Gf257::from_literal(0), 1000), None);
+    // 16 has order 4 modulo 257.
+    let h = Gf257::from_literal(16);
+    assert_eq!(h.discrete_log(h.pow(6), 1000), Some(2));
+    assert_eq!(h.discrete_log(Gf257::from_literal(3), 1000), None);
+    let z = Z1000Tables::from_literal(10);
+    assert_eq!(z.discrete_log(Z1000Tables::from_literal(0), 10), Some(3));
+}
+
+// RSA-like toy modulus 61 * 53.
+define_refined_modular_integer!(Z3233, ProtoNat, ProtoNat::from_literal(3233));
+define_unit_group!(Z3233Star, Z3233);
+
+#[test]
+fn unit_groups() {
+    use std::convert::TryFrom;
+    assert!(Z3233Star::new(Z3233::from_literal(61)).is_none());
+    assert!(Z3233Star::new(Z3233::from_literal(0)).is_none());
+    let err = Z3233Star::try_from(Z3233::from_literal(106)).unwrap_err();
+    assert_eq!(err.to_string(), "value 0x6a is not a unit of Z3233Star");
+    let m = Z3233Star::try_from(Z3233::from_literal(65)).unwrap();
+    // e = 17 and d = 2753 are inverse modulo lcm(60, 52) = 780.
+    let c = m.pow(17);
+    assert_eq!(Z3233::from(c), Z3233::from_literal(2790));
+    assert_eq!(c.pow(2753), m);
+    assert_eq!(m * m.inv(), Z3233Star::one());
+    assert_eq!(m.inv().value(), Z3233::from_literal(1144));
+}
+
+define_unit_group!(SecretScalarStar, SecretScalar);
+
+#[test]
+fn secret_unit_groups() {
+    use std::convert::TryFrom;
+    let x = SecretScalarStar::try_from(SecretScalar::from_literal(11)).unwrap();
+    assert_eq!(x.pow(1008), SecretScalarStar::one());
+    assert_eq!(x.pow(3).value(), SecretScalar::from_literal(1331 - 1009));
+    assert_eq!(x * x.inv(), SecretScalarStar::one());
+    let err = SecretScalarStar::try_from(SecretScalar::from_literal(0)).unwrap_err();
+    assert_eq!(err.to_string(), "secret value is not a unit of SecretScalarStar");
+}
+
+define_abstract_integer_checked!(NoDivNat, 64, no_div);
+define_refined_modular_integer!(StrictGf257, ProtoNat, ProtoNat::from_literal(257), no_div, no_ord);
+
+#[test]
+fn operator_exclusion() {
+    // The call to `check` is ambiguous, hence rejected, when `T` implements the operator.
+    trait AmbiguousIfDiv<A> {
+        fn check() {}
+    }
+    impl<T> AmbiguousIfDiv<()> for T {}
+    impl<T: Div> AmbiguousIfDiv<u8> for T {}
+    <NoDivNat as AmbiguousIfDiv<_>>::check();
+    <StrictGf257 as AmbiguousIfDiv<_>>::check();
+    <SecretScalar as AmbiguousIfDiv<_>>::check();
+    trait AmbiguousIfOrd<A> {
+        fn check() {}
+    }
+    impl<T> AmbiguousIfOrd<()> for T {}
+    impl<T: PartialOrd> AmbiguousIfOrd<u8> for T {}
+    <StrictGf257 as AmbiguousIfOrd<_>>::check();
+
+    let x = StrictGf257::from_literal(200);
+    assert_eq!(x + x, StrictGf257::from_literal(143));
+    assert_eq!(x.div_field(x), StrictGf257::from_literal(1));
+    assert_eq!(NoDivNat::from_literal(6) * NoDivNat::from_literal(7), NoDivNat::from_literal(42));
+    assert!(NoDivNat::from_literal(1) < NoDivNat::from_literal(2));
+}
+
+#[test]
+fn type_erased_values() {
+    let values = [
+        AnyInt::new(ProtoNat::from_literal(300)),
+        AnyInt::new(Gf257::from_literal(256)),
+        AnyInt::new(U32spec::from(7u32)),
+    ];
+    let names: Vec<_> = values.iter().map(|v| v.type_name()).collect();
+    assert_eq!(names, ["ProtoNat", "Gf257", "U32spec"]);
+    assert_eq!(values[1].value(), BigUint::from(256u32));
+    assert!(values[0].is::<ProtoNat>() && !values[0].is::<Gf257>());
+    assert_eq!(values[0].downcast::<ProtoNat>(), Some(ProtoNat::from_literal(300)));
+    assert_eq!(values[0].downcast::<Gf257>(), None);
+    assert_eq!(values[2].downcast_ref::<U32spec>(), Some(&U32spec::from(7u32)));
+    assert_eq!(values[1].clone(), values[1]);
+    assert_ne!(values[0], AnyInt::new(Nat128::from_literal(300)));
+    assert_eq!(format!("{:?}", values[0]), "ProtoNat(300)");
+    trait AmbiguousIfAny<A> {
+        fn check() {}
+    }
+    impl<T> AmbiguousIfAny<()> for T {}
+    impl<T: AnyAbstractInt> AmbiguousIfAny<u8> for T {}
+    <SecretKey as AmbiguousIfAny<_>>::check();
+    <SecretScalar as AmbiguousIfAny<_>>::check();
+    <SecretU8 as AmbiguousIfAny<_>>::check();
+}
+
+#[test]
+fn decimal_formatting() {
+    let check = |x: BigUint| assert_eq!(Scalar::from(x.clone()).to_string(), x.to_string());
+    check(BigUint::from(0u32));
+    let ten = BigUint::from(10u32);
+    for k in [1, 18, 19, 20, 38, 57, 100, 154] {
+        let p = num::pow(ten.clone(), k);
+        check(&p - 1u32);
+        check(p.clone());
+        check(p + 1u32);
+    }
+    check((BigUint::from(1u32) << 512) - 1u32);
+    check(BigUint::from(u64::MAX) * BigUint::from(u64::MAX));
+    assert_eq!(ProtoNat::from_literal(65535).to_string(), "65535");
+    let x = LittleNat::from_literal(1234567890123456789012345);
+    assert_eq!(x.to_string(), "1234567890123456789012345");
+}
+
+#[test]
+fn radix_digits() {
+    let x = SizeNatExample::from_literal(0x1234_5678_9abc);
+    assert_eq!(x.to_radix_digits(16), [12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1]);
+    assert_eq!(SizeNatExample::from_literal(1000).to_radix_digits(10), [0, 0, 0, 1]);
+    assert!(SizeNatExample::from_literal(0).to_radix_digits(7).is_empty());
+    let y = Scalar::from((BigUint::from(1u32) << 500) + 12345u32);
+    for radix in [2, 3, 10, 255, 1 << 16, u32::MAX] {
+        assert_eq!(Scalar::from_radix_digits(&y.to_radix_digits(radix), radix), y);
+    }
+    let z = SizeNatExample::from_radix_digits(&[1, 2, 3], 10);
+    assert_eq!(z, SizeNatExample::from_literal(321));
+    assert!(std::panic::catch_unwind(|| SizeNatExample::from_radix_digits(&[10], 10)).is_err());
+}
+
+#[test]
+fn naf_recoding() {
+    assert_eq!(SizeNatExample::from_literal(7).to_naf(), [-1, 0, 0, 1]);
+    assert!(SizeNatExample::from_literal(0).to_naf().is_empty());
+    let y = Scalar::from((BigUint::from(0xdead_beefu32) << 300) + 0x1234_5678u32);
+    for w in 2..=8 {
+        let digits = y.to_wnaf(w);
+        assert_eq!(recoding::from_signed_digits(&digits), BigUint::from(y));
+        assert_eq!(digits.last().map(|d| *d != 0), Some(true));
+        for (i, d) in digits.iter().enumerate().filter(|(_, d)| **d != 0) {
+            assert!(d % 2 != 0 && (d.unsigned_abs() as i32) < (1 << (w - 1)));
+            assert!(digits[i + 1..].iter().take(w - 1).all(|e| *e == 0));
+        }
+    }
+    let x = Gf257::from_literal(255);
+    assert_eq!(x.to_naf(), [-1, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(x.to_wnaf(4), [-1, 0, 0, 0, 0, 0, 0, 0, 1]);
+}
+
+#[test]
+fn constant_time_recoding() {
+    let len = recoding::signed_digits_len(Scalar::BITS, 4);
+    let values = [
+        BigUint::from(0u32),
+        BigUint::from(1u32),
+        (BigUint::from(1u32) << (Scalar::BITS - 1)) - 1u32,
+        Scalar::max_value(),
+    ];
+    for x in values.iter() {
+        for w in 2..=8 {
+            let digits = Scalar::from(x.clone()).to_signed_digits_ct(w);
+            assert_eq!(digits.len(), recoding::signed_digits_len(Scalar::BITS, w));
+            let half = 1i32 << (w - 1);
+            assert!(digits.iter().all(|d| (-half..half).contains(&(*d as i32))));
+            assert_eq!(recoding::from_signed_window_digits(&digits, w), *x);
+        }
+        assert_eq!(Scalar::from(x.clone()).to_signed_digits_ct(4).len(), len);
+    }
+    assert_eq!(SizeNatExample::from_literal(15).to_signed_digits_ct(4)[..3], [-1, 1, 0]);
+    let g = Gf257::from_literal(256).to_signed_digits_ct(8);
+    assert_eq!(recoding::from_signed_window_digits(&g, 8), BigUint::from(256u32));
+}
+
+#[test]
+fn reduction_of_digests() {
+    let digest: Vec<u8> = (0..64u32).map(|i| (i * 37 + 11) as u8).collect();
+    let p = BigUint::from(Felem::max());
+    let le = Felem::from_bytes_le_mod_order(&digest);
+    assert_eq!(BigUint::from(le), BigUint::from_bytes_le(&digest) % &p);
+    let be = Felem::from_bytes_be_mod_order(&digest);
+    assert_eq!(BigUint::from(be), BigUint::from_bytes_be(&digest) % &p);
+    assert_eq!(Felem::from_bytes_mod_order(&digest), be);
+    assert_eq!(Felem::from_bytes_mod_order(&[]), Felem::from_literal(0));
+    assert_eq!(Gf257::from_bytes_le_mod_order(&[1, 1, 1]), Gf257::from_literal(65793 % 257));
+}
+
+define_refined_modular_integer!(M89, Narrow, Narrow::pow2(89) - Narrow::from_literal(1));
+
+#[test]
+fn wide_reduction() {
+    let x = Narrow::pow2(99) + Narrow::from_literal(12345);
+    let y = Narrow::pow2(97) + Narrow::from_literal(777);
+    let expected = BigUint::from(x) * BigUint::from(y) % BigUint::from(M89::max());
+    assert_eq!(BigUint::from(M89::reduce_wide(x.mul_wide(y))), expected);
+    assert_eq!(M89::reduce_wide(NarrowWide::from_literal(5)), M89::from_literal(5));
+    let wide = BigBounded::pow2(200) + BigBounded::from_literal(3);
+    let expected = BigUint::from(wide) % 101u32;
+    assert_eq!(BigUint::from(HashableField::reduce_wide(wide)), expected);
+}
+
+#[cfg(feature = "xof")]
+#[test]
+fn seed_derivation() {
+    use sha3::digest::Update;
+    let xof = |seed: &[u8]| sha3::Shake256::default().chain(seed);
+    let x = Felem::derive_from_seed(xof(b"seed"), b"challenge");
+    assert_eq!(x, Felem::derive_from_seed(xof(b"seed"), b"challenge"));
+    assert_ne!(x, Felem::derive_from_seed(xof(b"seed"), b"nonce"));
+    assert_ne!(x, Felem::derive_from_seed(xof(b"seed2"), b"challenge"));
+    // the length prefix separates the seed from the domain
+    assert_ne!(
+        Gf257::derive_from_seed(xof(b"ab"), b"c"),
+        Gf257::derive_from_seed(xof(b"a"), b"bc")
+    );
+    let mut counts = [0u32; 257];
+    for i in 0..2570u32 {
+        let y = Gf257::derive_from_seed(xof(&i.to_be_bytes()), b"uniformity");
+        counts[num::ToPrimitive::to_usize(&BigUint::from(y)).unwrap()] += 1;
+    }
+    assert!(counts.iter().all(|c| *c < 40));
+}
+
+#[cfg(feature = "rfc6979")]
+define_refined_modular_integer!(
+    K163Scalar,
+    BigBounded,
+    BigBounded::from_hex("04000000000000000000020108a2e0cc0d99f8a5ef")
+);
+
+#[cfg(feature = "rfc6979")]
+#[test]
+fn rfc6979_nonces() {
+    use sha2::{Digest, Sha256};
+    type HmacSha256 = hmac::Hmac<Sha256>;
+    // RFC 6979, A.2.5: ECDSA on P-256 with SHA-256
+    let x = "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+    let x = P256Scalar::from_hex(x);
+    let k = P256Scalar::rfc6979_nonce::<HmacSha256>(x, &Sha256::digest(b"sample"));
+    let expected = "a6e3c57dd01abe90086538398355dd4c3b17aa873382b0f24d6129493d8aad60";
+    assert_eq!(k, P256Scalar::from_hex(expected));
+    let k = P256Scalar::rfc6979_nonce::<HmacSha256>(x, &Sha256::digest(b"test"));
+    let expected = "d16b6ae827f17175e040871a1c7ec3500192c4c92677336ec2537acaee0008e0";
+    assert_eq!(k, P256Scalar::from_hex(expected));
+    // RFC 6979, A.1.2: a 163-bit order, shorter than the hash
+    let x = K163Scalar::from_hex("009a4d6792295a7f730fc3f2b49cbc0f62e862272f");
+    let k = K163Scalar::rfc6979_nonce::<HmacSha256>(x, &Sha256::digest(b"sample"));
+    assert_eq!(k, K163Scalar::from_hex("023af4074c90a02b3fe61d286d5c87f425e6bdd81b"));
+}
@@ -0,0 +1,152 @@
+//! Statistical timing-leak detection in the style of dudect, enabled by the `timing` feature.
+//!
+//! An operation is timed on a fixed input and on random inputs, interleaved in a pseudo-random
+//! order; Welch's t-test then tells whether the two timing distributions differ. A large
+//! `|t|` is evidence that the running time depends on the input:
+//!
+//! ```no_run
+//! # use abstract_integers::*;
+//! # use num::{BigUint, CheckedSub, Zero};
+//! # use std::num::ParseIntError;
+//! # use std::ops::*;
+//! # define_abstract_integer_checked!(BigBounded, 256);
+//! # define_refined_modular_integer!(
+//! #     Felem,
+//! #     BigBounded,
+//! #     BigBounded::pow2(255) - BigBounded::from_literal(19)
+//! # );
+//! let mut state = 0x1234_5678_9abc_def0u64;
+//! let report = timing::measure(
+//!     Felem::from_literal(0),
+//!     || {
+//!         state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+//!         Felem::from(state)
+//!     },
+//!     |x| x.pow_ct(0xdead_beef),
+//!     100_000,
+//! );
+//! assert!(!report.leaks(), "{}", report);
+//! ```
+//!
+//! Timings are noisy: run the harness in release mode, on an idle machine, with enough samples.
+//! A report that does not leak is not a proof of constant-time execution.
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::Instant;
+
+/// Threshold on `|t|` above which dudect considers that the operation leaks.
+pub const T_THRESHOLD: f64 = 4.5;
+
+/// Fraction of the measurements kept, the slowest ones being discarded as noise.
+const KEPT_FRACTION: f64 = 0.9;
+
+/// Result of the comparison of the fixed-input and random-input timings.
+#[derive(Clone, Copy, Debug)]
+pub struct TimingReport {
+    /// Welch's t statistic of the two classes of measurements.
+    pub t: f64,
+    /// Number of measurements kept in each class.
+    pub samples: (usize, usize),
+}
+
+impl TimingReport {
+    /// Whether `|t|` exceeds `T_THRESHOLD`.
+    pub fn leaks(&self) -> bool {
+        self.t.abs() > T_THRESHOLD
+    }
+}
+
+impl fmt::Display for TimingReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "t = {:.2} over {} fixed and {} random measurements: {}",
+            self.t,
+            self.samples.0,
+            self.samples.1,
+            if self.leaks() {
+                "timing leak detected"
+            } else {
+                "no leak detected"
+            }
+        )
+    }
+}
+
+/// Running mean and variance (Welford's algorithm).
+#[derive(Default)]
+struct Moments {
+    n: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl Moments {
+    fn push(&mut self, x: f64) {
+        self.n += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.n as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    fn variance(&self) -> f64 {
+        if self.n < 2 {
+            0.0
+        } else {
+            self.m2 / (self.n - 1) as f64
+        }
+    }
+}
+
+fn welch_t(a: &Moments, b: &Moments) -> f64 {
+    let se = (a.variance() / a.n as f64 + b.variance() / b.n as f64).sqrt();
+    if se == 0.0 {
+        0.0
+    } else {
+        (a.mean - b.mean) / se
+    }
+}
+
+/// Times `op` `samples` times, each time on either `fixed` or a fresh input drawn from
+/// `random`, and compares the two classes with Welch's t-test.
+pub fn measure<I, R, F, O>(fixed: I, mut random: R, mut op: F, samples: usize) -> TimingReport
+where
+    I: Clone,
+    R: FnMut() -> I,
+    F: FnMut(I) -> O,
+{
+    // xorshift64 choosing the class of each measurement
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    let mut measurements = Vec::with_capacity(samples);
+    for _ in 0..samples {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let is_fixed = state & 1 == 0;
+        let input = if is_fixed { fixed.clone() } else { random() };
+        let start = Instant::now();
+        black_box(op(black_box(input)));
+        measurements.push((is_fixed, start.elapsed().as_nanos() as f64));
+    }
+    let mut sorted: Vec<f64> = measurements.iter().map(|&(_, t)| t).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let cutoff = sorted
+        .get((sorted.len() as f64 * KEPT_FRACTION) as usize)
+        .copied()
+        .unwrap_or(f64::INFINITY);
+    let (mut fixed_class, mut random_class) = (Moments::default(), Moments::default());
+    for (is_fixed, t) in measurements {
+        if t <= cutoff {
+            if is_fixed {
+                fixed_class.push(t)
+            } else {
+                random_class.push(t)
+            }
+        }
+    }
+    TimingReport {
+        t: welch_t(&fixed_class, &random_class),
+        samples: (fixed_class.n, random_class.n),
+    }
+}
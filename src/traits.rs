@@ -0,0 +1,100 @@
+//! Traits implemented by the types generated by the macros of this crate, to write code generic
+//! over them.
+
+use num::BigUint;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Mul, Not, Sub};
+
+/// Operations shared by the types defined with `define_refined_modular_integer`.
+pub trait ModularInteger:
+    Copy + Eq + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    /// The additive identity.
+    fn zero() -> Self;
+    /// The multiplicative identity.
+    fn one() -> Self;
+    /// Whether `/` is the field division rather than the floor division of the representatives.
+    const FIELD_DIVISION: bool = false;
+    /// Whether the type was defined with the `secret` option.
+    const SECRET: bool = false;
+    /// Number of bits of the representation of the values.
+    const BITS: usize;
+    /// Number of bytes of the canonical encoding of the values.
+    const BYTES: usize;
+
+    /// The modulus of all operations.
+    fn modulus() -> BigUint;
+    /// Multiplicative inverse, valid for prime moduli.
+    fn inv(self) -> Self;
+    /// Returns self to the power of the argument.
+    fn pow(self, exp: u128) -> Self;
+    /// Returns `self * self`.
+    fn square(self) -> Self {
+        self * self
+    }
+}
+
+/// Operations shared by the types defined with `define_abstract_integer_checked`.
+pub trait CheckedInteger:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    /// Number of bits of the representation, the values are below `2^BITS`.
+    const BITS: usize;
+    /// Number of bytes of the canonical encoding, `BITS` rounded up to a byte.
+    const BYTES: usize;
+    /// Whether the type was defined with the `secret` option.
+    const SECRET: bool = false;
+    /// The largest value of the type.
+    fn max_value() -> BigUint;
+}
+
+/// Double-width companions declared with `define_double_width`, such as the companion types
+/// generated by the `wide` option.
+pub trait DoubleWidth: CheckedInteger + Into<BigUint> {
+    /// The type of half the number of bits.
+    type Narrow: CheckedInteger;
+}
+
+/// Operations shared by the machine words defined with `define_machine_integer`.
+pub trait MachineInteger:
+    Copy
+    + Default
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+    + From<BigUint>
+{
+    /// Number of bits of the word.
+    const BITS: usize;
+    /// Whether the type was defined with the `secret` option.
+    const SECRET: bool;
+
+    /// Returns the value, explicitly making it public for secret words.
+    fn declassify(self) -> BigUint;
+}
+
+/// Natural integers with the regular arithmetic operations, implemented by the types defined
+/// with `define_abstract_integer_checked` and by `UnboundedNat`. Specifications written against
+/// this trait can start unbounded and be pinned to a bounded type later.
+pub trait AbstractInteger:
+    Clone
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + From<BigUint>
+    + Into<BigUint>
+{
+    /// Converts an integer literal.
+    fn from_literal(x: u128) -> Self;
+    /// The largest value of the type, `None` if it is unbounded.
+    fn bound() -> Option<BigUint>;
+}
+
+/// Types usable as exponents by `pow_scalar`, implemented by all the types generated by the
+/// macros of this crate. Exponents of group elements can then be given their own type, distinct
+/// from the type of the elements.
+pub trait Exponent: Copy + Into<BigUint> {}
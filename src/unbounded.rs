@@ -0,0 +1,116 @@
+//! Natural integers without upper bound, for the early stages of a specification.
+//!
+//! `UnboundedNat` implements `AbstractInteger` like the bounded types, so that code written
+//! against the trait can later be pinned to a bounded type without rewriting. It is a distinct
+//! type: mixing it with bounded values is a type error.
+
+use crate::diagnostics::{ArithmeticErrorKind, ArithmeticPanic};
+use crate::traits::AbstractInteger;
+use num::{BigUint, CheckedSub, Zero};
+use std::fmt;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+/// A natural integer without upper bound, see the module documentation.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct UnboundedNat(BigUint);
+
+impl UnboundedNat {
+    pub fn from_literal(x: u128) -> Self {
+        UnboundedNat(BigUint::from(x))
+    }
+}
+
+impl From<BigUint> for UnboundedNat {
+    fn from(x: BigUint) -> Self {
+        UnboundedNat(x)
+    }
+}
+
+impl From<UnboundedNat> for BigUint {
+    fn from(x: UnboundedNat) -> BigUint {
+        x.0
+    }
+}
+
+impl AbstractInteger for UnboundedNat {
+    fn from_literal(x: u128) -> Self {
+        UnboundedNat::from_literal(x)
+    }
+
+    fn bound() -> Option<BigUint> {
+        None
+    }
+}
+
+impl fmt::Display for UnboundedNat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Debug for UnboundedNat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Add for UnboundedNat {
+    type Output = UnboundedNat;
+    fn add(self, rhs: UnboundedNat) -> UnboundedNat {
+        UnboundedNat(self.0 + rhs.0)
+    }
+}
+
+/// **Warning**: panics on underflow.
+impl Sub for UnboundedNat {
+    type Output = UnboundedNat;
+    fn sub(self, rhs: UnboundedNat) -> UnboundedNat {
+        let c = self.0.checked_sub(&rhs.0).unwrap_or_else(|| {
+            ArithmeticPanic::new(
+                "UnboundedNat",
+                "-",
+                ArithmeticErrorKind::Underflow,
+                vec![self.0.clone(), rhs.0.clone()],
+            )
+            .recover::<UnboundedNat>()
+        });
+        UnboundedNat(c)
+    }
+}
+
+impl Mul for UnboundedNat {
+    type Output = UnboundedNat;
+    fn mul(self, rhs: UnboundedNat) -> UnboundedNat {
+        UnboundedNat(self.0 * rhs.0)
+    }
+}
+
+fn check_divisor(operation: &'static str, a: &BigUint, b: &BigUint) -> Option<UnboundedNat> {
+    if b.is_zero() {
+        let r = ArithmeticPanic::new(
+            "UnboundedNat",
+            operation,
+            ArithmeticErrorKind::DivisionByZero,
+            vec![a.clone(), b.clone()],
+        )
+        .recover::<UnboundedNat>();
+        return Some(UnboundedNat(r));
+    }
+    None
+}
+
+/// **Warning**: panics on division by 0.
+impl Div for UnboundedNat {
+    type Output = UnboundedNat;
+    fn div(self, rhs: UnboundedNat) -> UnboundedNat {
+        check_divisor("/", &self.0, &rhs.0).unwrap_or_else(|| UnboundedNat(self.0 / rhs.0))
+    }
+}
+
+/// **Warning**: panics on division by 0.
+impl Rem for UnboundedNat {
+    type Output = UnboundedNat;
+    fn rem(self, rhs: UnboundedNat) -> UnboundedNat {
+        check_divisor("%", &self.0, &rhs.0).unwrap_or_else(|| UnboundedNat(self.0 % rhs.0))
+    }
+}
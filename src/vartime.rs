@@ -0,0 +1,71 @@
+//! Explicitly variable-time views of the types defined with the `secret` option.
+//!
+//! The operations whose running time depends on the values (division, remainder, inversion and
+//! exponentiation other than `pow_ct`) are not implemented on secret types. They are only
+//! available on the `VarTime` view returned by `var_time()`, so that every variable-time use of
+//! a secret is visible in the code:
+//!
+//! ```
+//! # use abstract_integers::*;
+//! # use num::{BigUint, CheckedSub, Zero};
+//! # use std::num::ParseIntError;
+//! # use std::ops::*;
+//! define_abstract_integer_checked!(Key, 256, secret);
+//! # let (k, p) = (Key::from_literal(10), Key::from_literal(13));
+//! let q = k.var_time() / Key::from_literal(3);
+//! let i = k.var_time().inv(p);
+//! # assert_eq!((q, i), (Key::from_literal(3), Key::from_literal(4)));
+//! ```
+//!
+//! Refined types over a secret base are declared with the `secret` option as well, and their
+//! views implement `VarTimeModularOps` instead, the modulus being implicit.
+
+use crate::traits::Exponent;
+use num::BigUint;
+use std::fmt;
+
+/// Variable-time view of a value. `Display` and `Debug` print the actual value, even for secret
+/// types.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct VarTime<T>(pub T);
+
+impl<T> VarTime<T> {
+    /// Returns the underlying value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Copy + Into<BigUint>> fmt::Display for VarTime<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let uint: BigUint = self.0.into();
+        write!(f, "{}", uint)
+    }
+}
+
+impl<T: Copy + Into<BigUint>> fmt::Debug for VarTime<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Variable-time modular operations of the views of secret checked types, with the same
+/// semantics as the methods of the same name of the non-secret types.
+pub trait VarTimeOps<T> {
+    fn inv(self, modval: T) -> T;
+    fn pow_felem(self, exp: T, modval: T) -> T;
+    fn pow(self, exp: u128, modval: T) -> T;
+}
+
+/// Variable-time operations of the views of secret refined types, with the same semantics as
+/// the methods of the same name of the refined types over public bases.
+pub trait VarTimeModularOps<T> {
+    fn inv(self) -> T;
+    fn pow_felem(self, exp: T) -> T;
+    fn pow(self, exp: u128) -> T;
+    fn pow_scalar<E: Exponent>(self, exp: E) -> T;
+    fn pow_mod_signed(self, exp: i128) -> T;
+    fn pow_mod_big(self, exp: &BigUint) -> T;
+    fn div_floor(self, rhs: T) -> T;
+    fn div_field(self, rhs: T) -> T;
+}
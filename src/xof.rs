@@ -0,0 +1,50 @@
+//! Deterministic, domain-separated derivation of elements from an extendable-output function,
+//! for test-vector generation and Fiat–Shamir transcripts.
+//!
+//! The caller absorbs the seed into the XOF, such as SHAKE256, and `derive_below` absorbs the
+//! domain after it, prefixed with its length as 8 big-endian bytes so that no two domains
+//! collide. The element is then sampled by rejection: candidates of the bit length of the bound
+//! are read from the output until one is below the bound, which makes it exactly uniform.
+//!
+//! ```
+//! # use abstract_integers::*;
+//! # use num::{BigUint, CheckedSub, Zero};
+//! # use std::num::ParseIntError;
+//! # use std::ops::*;
+//! # define_abstract_integer_checked!(BigBounded, 256);
+//! # define_refined_modular_integer!(
+//! #     Felem,
+//! #     BigBounded,
+//! #     BigBounded::pow2(255) - BigBounded::from_literal(19)
+//! # );
+//! # use sha3::digest::Update;
+//! # use sha3::Shake256;
+//! # let seed = b"seed";
+//! let x = Felem::derive_from_seed(Shake256::default().chain(seed), b"challenge");
+//! ```
+
+use digest::{ExtendableOutput, Update, XofReader};
+use num::{BigUint, Zero};
+
+/// Returns an element uniformly distributed below `bound`, derived from the XOF and the domain.
+///
+/// **Warning**: panics if `bound` is 0.
+pub fn derive_below<X>(mut xof: X, domain: &[u8], bound: &BigUint) -> BigUint
+where
+    X: Update + ExtendableOutput,
+{
+    assert!(!bound.is_zero(), "no value below 0");
+    xof.update(&(domain.len() as u64).to_be_bytes());
+    xof.update(domain);
+    let mut reader = xof.finalize_xof();
+    let bits = bound.bits();
+    let mut bytes = vec![0u8; bits.div_ceil(8)];
+    loop {
+        reader.read(&mut bytes);
+        bytes[0] &= 0xff >> (8 * bytes.len() - bits);
+        let candidate = BigUint::from_bytes_be(&bytes);
+        if &candidate < bound {
+            return candidate;
+        }
+    }
+}